@@ -147,6 +147,30 @@ impl Light {
 	}
 }
 
+/// Full dataset (DAG) for an epoch. Mining with it is much faster than with the light cache,
+/// but it takes over 1GB of memory and a while to generate.
+pub struct Full {
+	dataset: Vec<Node>,
+}
+
+impl Full {
+	/// Generate the full dataset for the epoch of `light`.
+	pub fn new(light: &Light) -> Full {
+		let num_nodes = get_data_size(light.block_number) / NODE_BYTES;
+		Full {
+			dataset: (0..num_nodes as u32).map(|i| calculate_dag_item(i, &light.cache)).collect(),
+		}
+	}
+
+	/// Calculate the boundary data from the full dataset
+	/// `header_hash` - The header hash to pack into the mix
+	/// `nonce` - The nonce to pack into the mix
+	pub fn compute(&self, header_hash: &H256, nonce: u64) -> ProofOfWork {
+		let dataset: &[Node] = &self.dataset;
+		hash_compute(dataset.len() * NODE_BYTES, header_hash, nonce, |index| dataset[index as usize].clone())
+	}
+}
+
 pub struct SeedHashCompute {
 	prev_epoch: Cell<u64>,
 	prev_seedhash: Cell<H256>,
@@ -249,10 +273,12 @@ pub fn quick_get_difficulty(header_hash: &H256, nonce: u64, mix_hash: &H256) ->
 /// `nonce` - The nonce to pack into the mix
 pub fn light_compute(light: &Light, header_hash: &H256, nonce: u64) -> ProofOfWork {
 	let full_size = get_data_size(light.block_number);
-	hash_compute(light, full_size, header_hash, nonce)
+	let cache: &[Node] = &light.cache;  // deref once for better performance
+	hash_compute(full_size, header_hash, nonce, |index| calculate_dag_item(index, cache))
 }
 
-fn hash_compute(light: &Light, full_size: usize, header_hash: &H256, nonce: u64) -> ProofOfWork {
+/// `lookup` returns the dataset item with given index.
+fn hash_compute<F>(full_size: usize, header_hash: &H256, nonce: u64, lookup: F) -> ProofOfWork where F: Fn(u32) -> Node {
 	if full_size % MIX_WORDS != 0 {
 		panic!("Unaligned full size");
 	}
@@ -271,12 +297,11 @@ fn hash_compute(light: &Light, full_size: usize, header_hash: &H256, nonce: u64)
 
 		let page_size = 4 * MIX_WORDS;
 		let num_full_pages = (full_size / page_size) as u32;
-		let cache: &[Node] = &light.cache;  // deref once for better performance
 
 		for i in 0..(ETHASH_ACCESSES as u32) {
 			let index = fnv_hash(f_mix.get_unchecked(0).as_words().get_unchecked(0) ^ i, *mix.get_unchecked(0).as_words().get_unchecked((i as usize) % MIX_WORDS)) % num_full_pages;
 			for n in 0..MIX_NODES {
-				let tmp_node = calculate_dag_item(index * MIX_NODES as u32 + n as u32, cache);
+				let tmp_node = lookup(index * MIX_NODES as u32 + n as u32);
 				for w in 0..NODE_WORDS {
 					*mix.get_unchecked_mut(n).as_words_mut().get_unchecked_mut(w) = fnv_hash(*mix.get_unchecked(n).as_words().get_unchecked(w), *tmp_node.as_words().get_unchecked(w));
 				}
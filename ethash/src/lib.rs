@@ -25,7 +25,7 @@ extern crate log;
 mod compute;
 
 use std::mem;
-use compute::Light;
+use compute::{Light, Full};
 pub use compute::{ETHASH_EPOCH_LENGTH, H256, ProofOfWork, SeedHashCompute, quick_get_difficulty};
 
 use std::sync::Arc;
@@ -41,6 +41,7 @@ struct LightCache {
 /// Light/Full cache manager.
 pub struct EthashManager {
 	cache: Mutex<LightCache>,
+	full: Mutex<Option<(u64, Arc<Full>)>>,
 }
 
 impl EthashManager {
@@ -53,6 +54,7 @@ impl EthashManager {
 				prev_epoch: None,
 				prev: None,
 			}),
+			full: Mutex::new(None),
 		}
 	}
 
@@ -62,46 +64,75 @@ impl EthashManager {
 	/// `header_hash` - The header hash to pack into the mix
 	/// `nonce` - The nonce to pack into the mix
 	pub fn compute_light(&self, block_number: u64, header_hash: &H256, nonce: u64) -> ProofOfWork {
+		self.light(block_number).compute(header_hash, nonce)
+	}
+
+	/// Calculate the boundary data with the full dataset (DAG). Only the DAG of the most recent epoch is kept;
+	/// generating it takes a while, during which other callers of this method wait.
+	/// `block_number` - Block number to check
+	/// `header_hash` - The header hash to pack into the mix
+	/// `nonce` - The nonce to pack into the mix
+	pub fn compute_full(&self, block_number: u64, header_hash: &H256, nonce: u64) -> ProofOfWork {
 		let epoch = block_number / ETHASH_EPOCH_LENGTH;
-		let light = {
-			let mut lights = self.cache.lock();
-			let light = match lights.recent_epoch.clone() {
-				Some(ref e) if *e == epoch => lights.recent.clone(),
-				_ => match lights.prev_epoch.clone() {
-					Some(e) if e == epoch => {
-						// swap
-						let t = lights.prev_epoch;
-						lights.prev_epoch = lights.recent_epoch;
-						lights.recent_epoch = t;
-						let t = lights.prev.clone();
-						lights.prev = lights.recent.clone();
-						lights.recent = t;
-						lights.recent.clone()
-					}
-					_ => None,
-				},
+		let full = {
+			let mut full = self.full.lock();
+			let cached = match *full {
+				Some((e, ref dag)) if e == epoch => Some(dag.clone()),
+				_ => None,
 			};
-			match light {
+			match cached {
+				Some(dag) => dag,
 				None => {
-					let light = match Light::from_file(block_number) {
-						Ok(light) => Arc::new(light),
-						Err(e) => {
-							debug!("Light cache file not found for {}:{}", block_number, e);
-							let light = Light::new(block_number);
-							if let Err(e) = light.to_file() {
-								warn!("Light cache file write error: {}", e);
-							}
-							Arc::new(light)
-						}
-					};
-					lights.prev_epoch = mem::replace(&mut lights.recent_epoch, Some(epoch));
-					lights.prev = mem::replace(&mut lights.recent, Some(light.clone()));
-					light
+					debug!("Generating full dataset for epoch {}", epoch);
+					// drop the old dataset before generating the new one
+					*full = None;
+					let dag = Arc::new(Full::new(&self.light(block_number)));
+					*full = Some((epoch, dag.clone()));
+					dag
 				}
-				Some(light) => light,
 			}
 		};
-		light.compute(header_hash, nonce)
+		full.compute(header_hash, nonce)
+	}
+
+	fn light(&self, block_number: u64) -> Arc<Light> {
+		let epoch = block_number / ETHASH_EPOCH_LENGTH;
+		let mut lights = self.cache.lock();
+		let light = match lights.recent_epoch.clone() {
+			Some(ref e) if *e == epoch => lights.recent.clone(),
+			_ => match lights.prev_epoch.clone() {
+				Some(e) if e == epoch => {
+					// swap
+					let t = lights.prev_epoch;
+					lights.prev_epoch = lights.recent_epoch;
+					lights.recent_epoch = t;
+					let t = lights.prev.clone();
+					lights.prev = lights.recent.clone();
+					lights.recent = t;
+					lights.recent.clone()
+				}
+				_ => None,
+			},
+		};
+		match light {
+			None => {
+				let light = match Light::from_file(block_number) {
+					Ok(light) => Arc::new(light),
+					Err(e) => {
+						debug!("Light cache file not found for {}:{}", block_number, e);
+						let light = Light::new(block_number);
+						if let Err(e) = light.to_file() {
+							warn!("Light cache file write error: {}", e);
+						}
+						Arc::new(light)
+					}
+				};
+				lights.prev_epoch = mem::replace(&mut lights.recent_epoch, Some(epoch));
+				lights.prev = mem::replace(&mut lights.recent, Some(light.clone()));
+				light
+			}
+			Some(light) => light,
+		}
 	}
 }
 
@@ -140,13 +140,21 @@ impl Account {
 		}).1.clone()
 	}
 
+	/// Get the committed value of storage slot `key` along with a Merkle proof of it against the storage root.
+	/// Changes in the storage overlay are ignored.
+	pub fn prove_storage(&self, db: &AccountDB, key: &H256) -> (H256, Vec<Bytes>) {
+		let db = SecTrieDB::new(db, &self.storage_root)
+			.expect("Account storage_root is only altered by SecTrieDBMut, which keeps it valid; qed");
+		let (value, proof) = db.get_with_proof(key);
+		(H256::from(value.map_or(U256::zero(), |v| -> U256 {decode(v)})), proof)
+	}
+
 	/// return the balance associated with this account.
 	pub fn balance(&self) -> &U256 { &self.balance }
 
 	/// return the nonce associated with this account.
 	pub fn nonce(&self) -> &U256 { &self.nonce }
 
-	#[cfg(test)]
 	/// return the code hash associated with this account.
 	pub fn code_hash(&self) -> H256 {
 		self.code_hash.clone().unwrap_or(SHA3_EMPTY)
@@ -206,13 +214,18 @@ impl Account {
 	/// Determine whether there are any un-`commit()`-ed storage-setting operations.
 	pub fn storage_is_clean(&self) -> bool { self.storage_overlay.borrow().iter().find(|&(_, &(f, _))| f == Filth::Dirty).is_none() }
 
-	#[cfg(test)]
 	/// return the storage root associated with this account or None if it has been altered via the overlay.
 	pub fn storage_root(&self) -> Option<&H256> { if self.storage_is_clean() {Some(&self.storage_root)} else {None} }
 
 	/// return the storage overlay.
 	pub fn storage_overlay(&self) -> Ref<HashMap<H256, (Filth, H256)>> { self.storage_overlay.borrow() }
 
+	/// Set the nonce of the account to `nonce`.
+	pub fn set_nonce(&mut self, nonce: U256) {
+		self.nonce = nonce;
+		self.filth = Filth::Dirty;
+	}
+
 	/// Increment the nonce of the account by one.
 	pub fn inc_nonce(&mut self) {
 		self.nonce = self.nonce + U256::from(1u8);
@@ -159,9 +159,9 @@ impl Engine for BasicAuthority {
 		Ok(())
 	}
 
-	fn verify_transaction_basic(&self, t: &SignedTransaction, _header: &Header) -> result::Result<(), Error> {
+	fn verify_transaction_basic(&self, t: &SignedTransaction, header: &Header) -> result::Result<(), Error> {
 		try!(t.check_low_s());
-		Ok(())
+		t.check_chain_id(self.signing_chain_id(header.number()))
 	}
 
 	fn verify_transaction(&self, t: &SignedTransaction, _header: &Header) -> Result<(), Error> {
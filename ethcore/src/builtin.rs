@@ -23,14 +23,43 @@ use ethabi;
 use hackishlibsnarkbindings;
 use ethabi::spec::ParamType;
 use ethabi::Token;
+use error::SpecError;
+use num::{BigUint, Zero, One};
+
+/// Failure reported by a builtin contract, e.g. because of malformed input.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BuiltinError(pub &'static str);
+
+impl fmt::Display for BuiltinError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
+
+/// Result of executing a builtin contract.
+pub type BuiltinResult = Result<(), BuiltinError>;
+
+/// Executor of a builtin contract. Takes the input as the first argument and places the
+/// output into the second.
+pub type BuiltinExec = Box<Fn(&[u8], &mut [u8]) -> BuiltinResult>;
 
 /// Definition of a contract whose implementation is built-in.
 pub struct Builtin {
-	/// The gas cost of running this built-in for the given size of input data.
-	pub cost: Box<Fn(usize) -> U256>,	// TODO: U256 should be bignum.
+	/// Name of the built-in, as given in the chain spec.
+	pub name: String,
+	/// The gas cost of running this built-in for the given input data.
+	pub cost: Box<Fn(&[u8]) -> U256>,	// TODO: U256 should be bignum.
 	/// Run this built-in function with the input being the first argument and the output
-	/// being placed into the second.
-	pub execute: Box<Fn(&[u8], &mut [u8])>,
+	/// being placed into the second. An error means that the call should fail.
+	pub execute: BuiltinExec,
+	/// Block number from which this built-in is active.
+	pub activate_at: u64,
+	/// Whether calls to this built-in should be run ahead of time during block verification.
+	/// Such built-ins cache their results so that `execute` is cheap during enactment.
+	pub preverify: bool,
+	/// Sizes of the arguments decoded from the given input data; used for tracing only.
+	/// Empty if the input could not be decoded.
+	pub arg_sizes: Box<Fn(&[u8]) -> Vec<usize>>,
 }
 
 // Rust does not mark closurer that do not capture as Sync
@@ -40,40 +69,269 @@ unsafe impl Send for Builtin {}
 
 impl fmt::Debug for Builtin {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "<Builtin>")
+		write!(f, "<Builtin {}>", self.name)
 	}
 }
 
 impl Builtin {
-	/// Create a new object from components.
-	pub fn new(cost: Box<Fn(usize) -> U256>, execute: Box<Fn(&[u8], &mut [u8])>) -> Builtin {
-		Builtin {cost: cost, execute: execute}
+	/// Create a new object from components; `name` is used in traces.
+	/// The built-in is active from genesis and takes its whole input as a single argument.
+	pub fn new(name: &str, cost: Box<Fn(&[u8]) -> U256>, execute: BuiltinExec) -> Builtin {
+		Builtin {
+			name: name.to_owned(),
+			cost: cost,
+			execute: execute,
+			activate_at: 0,
+			preverify: false,
+			arg_sizes: Box::new(raw_arg_sizes),
+		}
 	}
 
 	/// Create a new object from a builtin-function name with a linear cost associated with input size.
+	/// Panics if `name` is not known to the default `BuiltinRegistry`.
 	pub fn from_named_linear(name: &str, base_cost: usize, word_cost: usize) -> Builtin {
-		let cost = Box::new(move|s: usize| -> U256 {
-			U256::from(base_cost) + U256::from(word_cost) * U256::from((s + 31) / 32)
-		});
-
-		Self::new(cost, new_builtin_exec(name))
+		Self::new(name, linear_cost(base_cost, word_cost), new_builtin_exec(name))
 	}
 
+	/// Whether the built-in is active at block `number`.
+	pub fn is_active(&self, number: u64) -> bool { number >= self.activate_at }
+
 	/// Simple forwarder for cost.
-	pub fn cost(&self, s: usize) -> U256 { (*self.cost)(s) }
+	pub fn cost(&self, input: &[u8]) -> U256 { (*self.cost)(input) }
 
 	/// Simple forwarder for execute.
-	pub fn execute(&self, input: &[u8], output: &mut[u8]) { (*self.execute)(input, output); }
+	pub fn execute(&self, input: &[u8], output: &mut[u8]) -> BuiltinResult { (*self.execute)(input, output) }
+
+	/// Simple forwarder for arg_sizes.
+	pub fn arg_sizes(&self, input: &[u8]) -> Vec<usize> { (*self.arg_sizes)(input) }
 }
 
 impl From<ethjson::spec::Builtin> for Builtin {
 	fn from(b: ethjson::spec::Builtin) -> Self {
-		match b.pricing {
-			ethjson::spec::Pricing::Linear(linear) => {
-				Self::from_named_linear(b.name.as_ref(), linear.base, linear.word)
-			}
+		BuiltinRegistry::default().builtin(b).unwrap_or_else(|e| panic!("{}", e))
+	}
+}
+
+/// Factory producing a fresh executor for a builtin contract.
+pub type BuiltinFactory = Box<Fn() -> BuiltinExec>;
+
+/// Decoder of the sizes of the arguments a builtin contract reads from its input; used for tracing.
+pub type ArgSizes = fn(&[u8]) -> Vec<usize>;
+
+struct Registration {
+	factory: BuiltinFactory,
+	arg_sizes: ArgSizes,
+	preverify: bool,
+}
+
+/// Registry of builtin contract implementations, keyed by the name used in the chain spec.
+///
+/// `BuiltinRegistry::default()` knows about all builtins shipped with this crate;
+/// embedders may register further precompiles before loading a `Spec`.
+pub struct BuiltinRegistry {
+	factories: HashMap<String, Registration>,
+}
+
+impl fmt::Debug for BuiltinRegistry {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "<BuiltinRegistry {:?}>", self.names())
+	}
+}
+
+impl Default for BuiltinRegistry {
+	fn default() -> Self {
+		let mut registry = BuiltinRegistry::new();
+		registry.register("identity", || Box::new(exec_identity));
+		registry.register("ecrecover", || Box::new(exec_ecrecover));
+		registry.register("sha256", || Box::new(exec_sha256));
+		registry.register("ripemd160", || Box::new(exec_ripemd160));
+		registry.register_preverified("zkSNARK", || {
+			let cache = VerificationCache::default();
+			Box::new(move |input: &[u8], output: &mut [u8]| exec_zksnark(&cache, input, output))
+		}, zksnark_arg_sizes);
+		registry.register("alt_bn128_add", || Box::new(exec_alt_bn128_add));
+		registry.register("alt_bn128_mul", || Box::new(exec_alt_bn128_mul));
+		registry.register("alt_bn128_pairing", || Box::new(exec_alt_bn128_pairing));
+		registry.register_with_arg_sizes("modexp", || Box::new(exec_modexp), modexp_arg_sizes);
+		registry
+	}
+}
+
+impl BuiltinRegistry {
+	/// Create a new registry with no builtins registered.
+	pub fn new() -> Self {
+		BuiltinRegistry {
+			factories: HashMap::new(),
 		}
 	}
+
+	/// Register a builtin under `name`, replacing any previous registration with that name.
+	/// Its whole input is traced as a single argument.
+	pub fn register<F>(&mut self, name: &str, factory: F) where F: Fn() -> BuiltinExec + 'static {
+		self.register_with_arg_sizes(name, factory, raw_arg_sizes);
+	}
+
+	/// Register a builtin under `name` whose input is split into arguments by `arg_sizes` when traced.
+	pub fn register_with_arg_sizes<F>(&mut self, name: &str, factory: F, arg_sizes: ArgSizes) where F: Fn() -> BuiltinExec + 'static {
+		self.factories.insert(name.to_owned(), Registration { factory: Box::new(factory), arg_sizes: arg_sizes, preverify: false });
+	}
+
+	/// Register a builtin under `name` whose calls are run ahead of time during block verification.
+	/// Its executor is expected to cache results, so that running it again during enactment is cheap.
+	pub fn register_preverified<F>(&mut self, name: &str, factory: F, arg_sizes: ArgSizes) where F: Fn() -> BuiltinExec + 'static {
+		self.factories.insert(name.to_owned(), Registration { factory: Box::new(factory), arg_sizes: arg_sizes, preverify: true });
+	}
+
+	/// Returns true if a builtin with given name is registered.
+	pub fn contains(&self, name: &str) -> bool {
+		self.factories.contains_key(name)
+	}
+
+	/// Names of all registered builtins.
+	pub fn names(&self) -> Vec<String> {
+		let mut names: Vec<_> = self.factories.keys().cloned().collect();
+		names.sort();
+		names
+	}
+
+	/// Create a new executor for builtin `name`, if registered.
+	pub fn executor(&self, name: &str) -> Option<BuiltinExec> {
+		self.factories.get(name).map(|r| (r.factory)())
+	}
+
+	/// Create a builtin from its chain spec description.
+	pub fn builtin(&self, b: ethjson::spec::Builtin) -> Result<Builtin, SpecError> {
+		let registration = try!(self.factories.get(&b.name).ok_or_else(|| SpecError::UnknownBuiltin(b.name.clone())));
+		let cost = match b.pricing {
+			ethjson::spec::Pricing::Linear(linear) => linear_cost(linear.base, linear.word),
+			ethjson::spec::Pricing::AbiBytes(abi) => abi_bytes_cost(abi.base, abi.words),
+			ethjson::spec::Pricing::PublicInputs(inputs) => public_inputs_cost(inputs.base, inputs.input),
+			ethjson::spec::Pricing::AltBn128Pairing(pairing) => alt_bn128_pairing_cost(pairing.base, pairing.pair),
+			ethjson::spec::Pricing::Modexp(modexp) => modexp_cost(modexp.divisor),
+		};
+		let mut builtin = Builtin::new(&b.name, cost, (registration.factory)());
+		builtin.arg_sizes = Box::new(registration.arg_sizes);
+		builtin.activate_at = b.activate_at.map_or(0, Into::into);
+		builtin.preverify = registration.preverify;
+		Ok(builtin)
+	}
+}
+
+fn linear_cost(base_cost: usize, word_cost: usize) -> Box<Fn(&[u8]) -> U256> {
+	Box::new(move|input: &[u8]| -> U256 {
+		U256::from(base_cost) + U256::from(word_cost) * U256::from((input.len() + 31) / 32)
+	})
+}
+
+/// Cost of `base_cost` plus, for each ABI-encoded `bytes` argument, its length in words
+/// times the corresponding entry of `word_costs`. Undecodable input costs just `base_cost`;
+/// executing it will fail anyway.
+fn abi_bytes_cost(base_cost: usize, word_costs: Vec<usize>) -> Box<Fn(&[u8]) -> U256> {
+	Box::new(move|input: &[u8]| -> U256 {
+		let base = U256::from(base_cost);
+		match decode_abi_bytes(input, word_costs.len()) {
+			Ok(args) => args.iter().zip(word_costs.iter()).fold(base, |acc, (arg, word_cost)| {
+				acc + U256::from(*word_cost) * U256::from((arg.len() + 31) / 32)
+			}),
+			Err(_) => base,
+		}
+	})
+}
+
+/// Cost of `base_cost` plus `input_cost` for each public input of a zkSNARK verification.
+/// Public inputs are counted as 32-byte field elements of the primary input argument.
+fn public_inputs_cost(base_cost: usize, input_cost: usize) -> Box<Fn(&[u8]) -> U256> {
+	Box::new(move|input: &[u8]| -> U256 {
+		let inputs = decode_abi_bytes(input, 3).map(|args| (args[2].len() + 31) / 32).unwrap_or(0);
+		U256::from(base_cost) + U256::from(input_cost) * U256::from(inputs)
+	})
+}
+
+/// Cost of `base_cost` plus `pair_cost` for each (G1, G2) pair of an alt_bn128 pairing check.
+fn alt_bn128_pairing_cost(base_cost: usize, pair_cost: usize) -> Box<Fn(&[u8]) -> U256> {
+	Box::new(move|input: &[u8]| -> U256 {
+		U256::from(base_cost) + U256::from(pair_cost) * U256::from(input.len() / ALT_BN128_PAIR_LEN)
+	})
+}
+
+/// Cost of modular exponentiation: the complexity of multiplying numbers as long as the
+/// longer of base and modulus, times the bit length of the exponent, over `divisor`.
+fn modexp_cost(divisor: usize) -> Box<Fn(&[u8]) -> U256> {
+	Box::new(move|input: &[u8]| -> U256 {
+		let base_len = U256::from(&H256::from_slice(&read_word(input, 0)));
+		let exp_len = U256::from(&H256::from_slice(&read_word(input, 1)));
+		let mod_len = U256::from(&H256::from_slice(&read_word(input, 2)));
+
+		if base_len.is_zero() && mod_len.is_zero() {
+			return U256::zero();
+		}
+
+		// larger lengths would never fit into memory anyway
+		let max_len = U256::from(u32::max_value() / 2);
+		if base_len > max_len || exp_len > max_len || mod_len > max_len {
+			return !U256::zero();
+		}
+		let (base_len, exp_len, mod_len) = (base_len.low_u64(), exp_len.low_u64(), mod_len.low_u64());
+
+		// the first 32 bytes of the exponent, read as a number
+		let mut exp_head = [0u8; 32];
+		let exp_head_len = min(exp_len, 32) as usize;
+		if 96 + base_len < input.len() as u64 {
+			copy_to(&input[96 + base_len as usize..], &mut exp_head[32 - exp_head_len..]);
+		}
+		let exp_head = U256::from(&H256::from_slice(&exp_head));
+		let exp_head_bits = if exp_head.is_zero() { 0 } else { exp_head.bits() as u64 - 1 };
+		let adjusted_exp_len = match exp_len {
+			x if x <= 32 => exp_head_bits,
+			x => 8 * (x - 32) + exp_head_bits,
+		};
+
+		let complexity = modexp_mult_complexity(max(base_len, mod_len));
+		let (gas, overflow) = U256::from(complexity).overflowing_mul(U256::from(max(adjusted_exp_len, 1)));
+		if overflow {
+			return !U256::zero();
+		}
+		gas / U256::from(divisor)
+	})
+}
+
+fn modexp_mult_complexity(x: u64) -> u64 {
+	match x {
+		x if x <= 64 => x * x,
+		x if x <= 1024 => (x * x) / 4 + 96 * x - 3072,
+		x => (x * x) / 16 + 480 * x - 199680,
+	}
+}
+
+fn raw_arg_sizes(input: &[u8]) -> Vec<usize> {
+	vec![input.len()]
+}
+
+/// Lengths of the verification key, proof and primary input of a zkSNARK verification.
+fn zksnark_arg_sizes(input: &[u8]) -> Vec<usize> {
+	decode_abi_bytes(input, 3).map(|args| args.iter().map(Vec::len).collect()).unwrap_or_else(|_| vec![])
+}
+
+/// Declared lengths of base, exponent and modulus of a modexp call.
+fn modexp_arg_sizes(input: &[u8]) -> Vec<usize> {
+	(0..3).map(|i| {
+		let len = U256::from(&H256::from_slice(&read_word(input, i)));
+		if len > U256::from(u32::max_value()) { u32::max_value() as usize } else { len.low_u64() as usize }
+	}).collect()
+}
+
+/// Decode input consisting of a 4-byte function selector followed by `count`
+/// ABI-encoded `bytes` arguments.
+fn decode_abi_bytes(input: &[u8], count: usize) -> Result<Vec<Bytes>, BuiltinError> {
+	if input.len() < 4 {
+		return Err(BuiltinError("input too short"));
+	}
+	let types: Vec<_> = (0..count).map(|_| ParamType::Bytes).collect();
+	let tokens = try!(ethabi::Decoder::decode(&types, input[4..].to_vec()).map_err(|_| BuiltinError("invalid ABI encoding")));
+	tokens.into_iter().map(|token| match token {
+		Token::Bytes(bytes) => Ok(bytes),
+		_ => Err(BuiltinError("invalid ABI encoding")),
+	}).collect()
 }
 
 /// Copy a bunch of bytes to a destination; if the `src` is too small to fill `dest`,
@@ -85,84 +343,274 @@ pub fn copy_to(src: &[u8], dest: &mut[u8]) {
 	}
 }
 
-/// Create a new builtin executor according to `name`.
-/// TODO: turn in to a factory with dynamic registration.
-pub fn new_builtin_exec(name: &str) -> Box<Fn(&[u8], &mut [u8])> {
-	match name {
-        "zkSNARK" => Box::new(move|input: &[u8], output: &mut[u8]| {
-            let outlen = output.len();
-            for i in 0..output.len() {
-                output[i] = 0;
-            }
-            let abitype = [ParamType::Bytes, ParamType::Bytes, ParamType::Bytes];
-            let v = input[4..].to_vec();
-            let decode = ethabi::Decoder::decode(&abitype, v);
-            if let Ok(tokens) = decode {
-                if tokens.len() == 3 {
-                    if let Token::Bytes(ref v1) = tokens[0] {
-                        if let Token::Bytes(ref v2) = tokens[1] {
-                            if let Token::Bytes(ref v3) = tokens[2] {
-                                let res = hackishlibsnarkbindings::snark_verify(v1, v2, v3);
-                                if res {
-                                    output[outlen - 1] = 1;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-		}),
-		"identity" => Box::new(move|input: &[u8], output: &mut[u8]| {
-			for i in 0..min(input.len(), output.len()) {
-				output[i] = input[i];
+/// Create a new builtin executor according to `name`, using the default `BuiltinRegistry`.
+/// Panics if no builtin with that name is registered.
+pub fn new_builtin_exec(name: &str) -> BuiltinExec {
+	BuiltinRegistry::default().executor(name).unwrap_or_else(|| panic!("invalid builtin name {}", name))
+}
+
+/// Maximal number of cached zkSNARK verification results.
+const VERIFICATION_CACHE_SIZE: usize = 4096;
+
+/// Results of zkSNARK verifications keyed by input hash, so that proofs verified
+/// during block verification need not be verified again during enactment.
+struct VerificationCache {
+	results: RwLock<HashMap<H256, Result<bool, BuiltinError>>>,
+}
+
+impl Default for VerificationCache {
+	fn default() -> Self {
+		VerificationCache {
+			results: RwLock::new(HashMap::new()),
+		}
+	}
+}
+
+impl VerificationCache {
+	fn get_or_verify<F>(&self, input: &[u8], verify: F) -> Result<bool, BuiltinError> where F: FnOnce(&[u8]) -> Result<bool, BuiltinError> {
+		let key = input.sha3();
+		if let Some(result) = self.results.read().get(&key) {
+			return result.clone();
+		}
+		let result = verify(input);
+		let mut results = self.results.write();
+		// results are only needed until the block is enacted, so just start afresh when full
+		if results.len() >= VERIFICATION_CACHE_SIZE {
+			results.clear();
+		}
+		results.insert(key, result.clone());
+		result
+	}
+}
+
+fn verify_snark(input: &[u8]) -> Result<bool, BuiltinError> {
+	let args = try!(decode_abi_bytes(input, 3));
+	Ok(hackishlibsnarkbindings::snark_verify(&args[0], &args[1], &args[2]))
+}
+
+fn exec_zksnark(cache: &VerificationCache, input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	for i in 0..output.len() {
+		output[i] = 0;
+	}
+	if try!(cache.get_or_verify(input, verify_snark)) {
+		if let Some(last) = output.last_mut() {
+			*last = 1;
+		}
+	}
+	Ok(())
+}
+
+/// Length of a single (G1, G2) pair in alt_bn128 pairing input.
+const ALT_BN128_PAIR_LEN: usize = 192;
+
+/// Order of the alt_bn128 groups, i.e. the modulus of the scalar field `Fr`.
+const ALT_BN128_GROUP_ORDER: U256 = U256([0x43e1f593f0000001, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029]);
+
+/// Read 32-byte word `index` of `input`, which is treated as if it were zero-extended.
+fn read_word(input: &[u8], index: usize) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	if index * 32 < input.len() {
+		copy_to(&input[index * 32..], &mut word);
+	}
+	word
+}
+
+fn read_fq(input: &[u8], index: usize) -> Result<bn::Fq, BuiltinError> {
+	bn::Fq::from_slice(&read_word(input, index)).map_err(|_| BuiltinError("invalid field element"))
+}
+
+/// Read a G1 point from words `index` and `index + 1`; (0, 0) is the point at infinity.
+fn read_g1(input: &[u8], index: usize) -> Result<bn::G1, BuiltinError> {
+	use bn::{AffineG1, G1, Group};
+
+	let x = try!(read_fq(input, index));
+	let y = try!(read_fq(input, index + 1));
+	if x.is_zero() && y.is_zero() {
+		Ok(G1::zero())
+	} else {
+		AffineG1::new(x, y).map(Into::into).map_err(|_| BuiltinError("invalid curve point"))
+	}
+}
+
+/// Read a G2 point from words `index` to `index + 3`. Each coordinate is encoded with
+/// its imaginary part first; all zeros is the point at infinity.
+fn read_g2(input: &[u8], index: usize) -> Result<bn::G2, BuiltinError> {
+	use bn::{AffineG2, Fq2, G2, Group};
+
+	let x_im = try!(read_fq(input, index));
+	let x_re = try!(read_fq(input, index + 1));
+	let y_im = try!(read_fq(input, index + 2));
+	let y_re = try!(read_fq(input, index + 3));
+	let x = Fq2::new(x_re, x_im);
+	let y = Fq2::new(y_re, y_im);
+	if x.is_zero() && y.is_zero() {
+		Ok(G2::zero())
+	} else {
+		AffineG2::new(x, y).map(Into::into).map_err(|_| BuiltinError("invalid curve point"))
+	}
+}
+
+/// Write G1 point `p` to `output` as affine coordinates; the point at infinity is (0, 0).
+fn write_g1(p: bn::G1, output: &mut [u8]) {
+	let mut buf = [0u8; 64];
+	if let Some(p) = bn::AffineG1::from_jacobian(p) {
+		p.x().to_big_endian(&mut buf[0..32]).expect("buffer is 32 bytes long; qed");
+		p.y().to_big_endian(&mut buf[32..64]).expect("buffer is 32 bytes long; qed");
+	}
+	copy_to(&buf, output);
+}
+
+fn exec_alt_bn128_add(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	let p1 = try!(read_g1(input, 0));
+	let p2 = try!(read_g1(input, 2));
+	write_g1(p1 + p2, output);
+	Ok(())
+}
+
+fn exec_alt_bn128_mul(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	let p = try!(read_g1(input, 0));
+	// any 256-bit scalar is valid; multiplying by it is the same as by its residue modulo the group order
+	let mut s = [0u8; 32];
+	(U256::from(&read_word(input, 2)[..]) % ALT_BN128_GROUP_ORDER).to_big_endian(&mut s);
+	let s = bn::Fr::from_slice(&s).expect("scalar is reduced modulo the group order; qed");
+	write_g1(p * s, output);
+	Ok(())
+}
+
+fn exec_alt_bn128_pairing(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	use bn::{Gt, pairing};
+
+	if input.len() % ALT_BN128_PAIR_LEN != 0 {
+		return Err(BuiltinError("input length must be a multiple of 192"));
+	}
+	let mut product = Gt::one();
+	for pair in input.chunks(ALT_BN128_PAIR_LEN) {
+		let a = try!(read_g1(pair, 0));
+		let b = try!(read_g2(pair, 2));
+		product = product * pairing(a, b);
+	}
+	let mut ret = H256::new();
+	if product == Gt::one() {
+		ret.as_slice_mut()[31] = 1;
+	}
+	copy_to(&ret, output);
+	Ok(())
+}
+
+/// Input is the lengths of base, exponent and modulus as 32-byte words followed by the
+/// numbers themselves, big-endian. Output is the result, left-padded to the modulus length.
+fn exec_modexp(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	// lengths too large to fit a `u64` could never be paid for
+	let base_len = U256::from(&H256::from_slice(&read_word(input, 0))).low_u64() as usize;
+	let exp_len = U256::from(&H256::from_slice(&read_word(input, 1))).low_u64() as usize;
+	let mod_len = U256::from(&H256::from_slice(&read_word(input, 2))).low_u64() as usize;
+
+	// cost is zero when both base and modulus are empty, however long the exponent
+	let result = if base_len == 0 && mod_len == 0 {
+		BigUint::zero()
+	} else {
+		let read_bytes = |offset: usize, len: usize| -> Bytes {
+			let mut bytes = vec![0u8; len];
+			if offset < input.len() {
+				copy_to(&input[offset..], &mut bytes);
 			}
-		}),
-		"ecrecover" => Box::new(move|input: &[u8], output: &mut[u8]| {
-			#[repr(packed)]
-			#[derive(Debug)]
-			struct InType {
-				hash: H256,
-				v: H256,
-				r: H256,
-				s: H256,
+			bytes
+		};
+		let base = BigUint::from_bytes_be(&read_bytes(96, base_len));
+		let exp = read_bytes(96 + base_len, exp_len);
+		let modulus = BigUint::from_bytes_be(&read_bytes(96 + base_len + exp_len, mod_len));
+		mod_pow(base, &exp, modulus)
+	};
+
+	let bytes = result.to_bytes_be();
+	// always true, except when the modulus is empty and the result is one-byte zero
+	if bytes.len() <= mod_len {
+		let mut ret = vec![0u8; mod_len];
+		copy_to(&bytes, &mut ret[mod_len - bytes.len()..]);
+		copy_to(&ret, output);
+	}
+	Ok(())
+}
+
+/// Left-to-right binary exponentiation of `base` to the big-endian `exp`, modulo `modulus`.
+fn mod_pow(base: BigUint, exp: &[u8], modulus: BigUint) -> BigUint {
+	// n^m % 0 || n^m % 1
+	if modulus <= BigUint::one() {
+		return BigUint::zero();
+	}
+
+	// n^0 % m
+	if exp.iter().all(|b| *b == 0) {
+		return BigUint::one();
+	}
+
+	let base = base % &modulus;
+	let mut result = BigUint::one();
+	for byte in exp.iter().skip_while(|b| **b == 0) {
+		for bit in (0..8).rev() {
+			result = &result * &result % &modulus;
+			if (byte >> bit) & 1 == 1 {
+				result = result * &base % &modulus;
 			}
-			let mut it: InType = InType { hash: H256::new(), v: H256::new(), r: H256::new(), s: H256::new() };
-			it.copy_raw(input);
-			if it.v == H256::from(&U256::from(27)) || it.v == H256::from(&U256::from(28)) {
-				let s = Signature::from_rsv(&it.r, &it.s, it.v[31] - 27);
-				if ec::is_valid(&s) {
-					if let Ok(p) = ec::recover(&s, &it.hash) {
-						let r = p.as_slice().sha3();
-						// NICE: optimise and separate out into populate-like function
-						for i in 0..min(32, output.len()) {
-							output[i] = if i < 12 {0} else {r[i]};
-						}
-					}
+		}
+	}
+	result
+}
+
+fn exec_identity(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	for i in 0..min(input.len(), output.len()) {
+		output[i] = input[i];
+	}
+	Ok(())
+}
+
+// Invalid signatures are not an error: by consensus the output is just left untouched.
+fn exec_ecrecover(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	#[repr(packed)]
+	#[derive(Debug)]
+	struct InType {
+		hash: H256,
+		v: H256,
+		r: H256,
+		s: H256,
+	}
+	let mut it: InType = InType { hash: H256::new(), v: H256::new(), r: H256::new(), s: H256::new() };
+	it.copy_raw(input);
+	if it.v == H256::from(&U256::from(27)) || it.v == H256::from(&U256::from(28)) {
+		let s = Signature::from_rsv(&it.r, &it.s, it.v[31] - 27);
+		if ec::is_valid(&s) {
+			if let Ok(p) = ec::recover(&s, &it.hash) {
+				let r = p.as_slice().sha3();
+				// NICE: optimise and separate out into populate-like function
+				for i in 0..min(32, output.len()) {
+					output[i] = if i < 12 {0} else {r[i]};
 				}
 			}
-		}),
-		"sha256" => Box::new(move|input: &[u8], output: &mut[u8]| {
-			let mut sha = Sha256::new();
-			sha.input(input);
-			if output.len() >= 32 {
-				sha.result(output);
-			} else {
-				let mut ret = H256::new();
-				sha.result(ret.as_slice_mut());
-				copy_to(&ret, output);
-			}
-		}),
-		"ripemd160" => Box::new(move|input: &[u8], output: &mut[u8]| {
-			let mut sha = Ripemd160::new();
-			sha.input(input);
-			let mut ret = H256::new();
-			sha.result(&mut ret.as_slice_mut()[12..32]);
-			copy_to(&ret, output);
-		}),
-		_ => {
-			panic!("invalid builtin name {}", name);
 		}
 	}
+	Ok(())
+}
+
+fn exec_sha256(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	let mut sha = Sha256::new();
+	sha.input(input);
+	if output.len() >= 32 {
+		sha.result(output);
+	} else {
+		let mut ret = H256::new();
+		sha.result(ret.as_slice_mut());
+		copy_to(&ret, output);
+	}
+	Ok(())
+}
+
+fn exec_ripemd160(input: &[u8], output: &mut [u8]) -> BuiltinResult {
+	let mut sha = Ripemd160::new();
+	sha.input(input);
+	let mut ret = H256::new();
+	sha.result(&mut ret.as_slice_mut()[12..32]);
+	copy_to(&ret, output);
+	Ok(())
 }
 
 #[test]
@@ -171,15 +619,15 @@ fn identity() {
 	let i = [0u8, 1, 2, 3];
 
 	let mut o2 = [255u8; 2];
-	f(&i[..], &mut o2[..]);
+	f(&i[..], &mut o2[..]).unwrap();
 	assert_eq!(i[0..2], o2);
 
 	let mut o4 = [255u8; 4];
-	f(&i[..], &mut o4[..]);
+	f(&i[..], &mut o4[..]).unwrap();
 	assert_eq!(i, o4);
 
 	let mut o8 = [255u8; 8];
-	f(&i[..], &mut o8[..]);
+	f(&i[..], &mut o8[..]).unwrap();
 	assert_eq!(i, o8[..4]);
 	assert_eq!([255u8; 4], o8[4..]);
 }
@@ -191,15 +639,15 @@ fn sha256() {
 	let i = [0u8; 0];
 
 	let mut o = [255u8; 32];
-	f(&i[..], &mut o[..]);
+	f(&i[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855").unwrap())[..]);
 
 	let mut o8 = [255u8; 8];
-	f(&i[..], &mut o8[..]);
+	f(&i[..], &mut o8[..]).unwrap();
 	assert_eq!(&o8[..], &(FromHex::from_hex("e3b0c44298fc1c14").unwrap())[..]);
 
 	let mut o34 = [255u8; 34];
-	f(&i[..], &mut o34[..]);
+	f(&i[..], &mut o34[..]).unwrap();
 	assert_eq!(&o34[..], &(FromHex::from_hex("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855ffff").unwrap())[..]);
 }
 
@@ -210,15 +658,15 @@ fn ripemd160() {
 	let i = [0u8; 0];
 
 	let mut o = [255u8; 32];
-	f(&i[..], &mut o[..]);
+	f(&i[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("0000000000000000000000009c1185a5c5e9fc54612808977ee8f548b2258d31").unwrap())[..]);
 
 	let mut o8 = [255u8; 8];
-	f(&i[..], &mut o8[..]);
+	f(&i[..], &mut o8[..]).unwrap();
 	assert_eq!(&o8[..], &(FromHex::from_hex("0000000000000000").unwrap())[..]);
 
 	let mut o34 = [255u8; 34];
-	f(&i[..], &mut o34[..]);
+	f(&i[..], &mut o34[..]).unwrap();
 	assert_eq!(&o34[..], &(FromHex::from_hex("0000000000000000000000009c1185a5c5e9fc54612808977ee8f548b2258d31ffff").unwrap())[..]);
 }
 
@@ -237,46 +685,46 @@ fn ecrecover() {
 	let i = FromHex::from_hex("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001b650acf9d3f5f0a2c799776a1254355d5f4061762a237396a99a0e0e3fc2bcd6729514a0dacb2e623ac4abd157cb18163ff942280db4d5caad66ddf941ba12e03").unwrap();
 
 	let mut o = [255u8; 32];
-	f(&i[..], &mut o[..]);
+	f(&i[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("000000000000000000000000c08b5542d177ac6686946920409741463a15dddb").unwrap())[..]);
 
 	let mut o8 = [255u8; 8];
-	f(&i[..], &mut o8[..]);
+	f(&i[..], &mut o8[..]).unwrap();
 	assert_eq!(&o8[..], &(FromHex::from_hex("0000000000000000").unwrap())[..]);
 
 	let mut o34 = [255u8; 34];
-	f(&i[..], &mut o34[..]);
+	f(&i[..], &mut o34[..]).unwrap();
 	assert_eq!(&o34[..], &(FromHex::from_hex("000000000000000000000000c08b5542d177ac6686946920409741463a15dddbffff").unwrap())[..]);
 
 	let i_bad = FromHex::from_hex("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001a650acf9d3f5f0a2c799776a1254355d5f4061762a237396a99a0e0e3fc2bcd6729514a0dacb2e623ac4abd157cb18163ff942280db4d5caad66ddf941ba12e03").unwrap();
 	let mut o = [255u8; 32];
-	f(&i_bad[..], &mut o[..]);
+	f(&i_bad[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap())[..]);
 
 	let i_bad = FromHex::from_hex("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001b000000000000000000000000000000000000000000000000000000000000001b0000000000000000000000000000000000000000000000000000000000000000").unwrap();
 	let mut o = [255u8; 32];
-	f(&i_bad[..], &mut o[..]);
+	f(&i_bad[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap())[..]);
 
 	let i_bad = FromHex::from_hex("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001b0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001b").unwrap();
 	let mut o = [255u8; 32];
-	f(&i_bad[..], &mut o[..]);
+	f(&i_bad[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap())[..]);
 
 	let i_bad = FromHex::from_hex("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001bffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff000000000000000000000000000000000000000000000000000000000000001b").unwrap();
 	let mut o = [255u8; 32];
-	f(&i_bad[..], &mut o[..]);
+	f(&i_bad[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap())[..]);
 
 	let i_bad = FromHex::from_hex("47173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001b000000000000000000000000000000000000000000000000000000000000001bffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap();
 	let mut o = [255u8; 32];
-	f(&i_bad[..], &mut o[..]);
+	f(&i_bad[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap())[..]);
 
 	// TODO: Should this (corrupted version of the above) fail rather than returning some address?
 /*	let i_bad = FromHex::from_hex("48173285a8d7341e5e972fc677286384f802f8ef42a5ec5f03bbfa254cb01fad000000000000000000000000000000000000000000000000000000000000001b650acf9d3f5f0a2c799776a1254355d5f4061762a237396a99a0e0e3fc2bcd6729514a0dacb2e623ac4abd157cb18163ff942280db4d5caad66ddf941ba12e03").unwrap();
 	let mut o = [255u8; 32];
-	f(&i_bad[..], &mut o[..]);
+	f(&i_bad[..], &mut o[..]).unwrap();
 	assert_eq!(&o[..], &(FromHex::from_hex("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap())[..]);*/
 }
 
@@ -289,14 +737,14 @@ fn from_unknown_linear() {
 #[test]
 fn from_named_linear() {
 	let b = Builtin::from_named_linear("identity", 10, 20);
-	assert_eq!((*b.cost)(0), U256::from(10));
-	assert_eq!((*b.cost)(1), U256::from(30));
-	assert_eq!((*b.cost)(32), U256::from(30));
-	assert_eq!((*b.cost)(33), U256::from(50));
+	assert_eq!(b.cost(&[0u8; 0]), U256::from(10));
+	assert_eq!(b.cost(&[0u8; 1]), U256::from(30));
+	assert_eq!(b.cost(&[0u8; 32]), U256::from(30));
+	assert_eq!(b.cost(&[0u8; 33]), U256::from(50));
 
 	let i = [0u8, 1, 2, 3];
 	let mut o = [255u8; 4];
-	(*b.execute)(&i[..], &mut o[..]);
+	(*b.execute)(&i[..], &mut o[..]).unwrap();
 	assert_eq!(i, o);
 }
 
@@ -307,16 +755,450 @@ fn from_json() {
 		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
 			base: 10,
 			word: 20,
-		})
+		}),
+		activate_at: None,
 	});
 
-	assert_eq!((*b.cost)(0), U256::from(10));
-	assert_eq!((*b.cost)(1), U256::from(30));
-	assert_eq!((*b.cost)(32), U256::from(30));
-	assert_eq!((*b.cost)(33), U256::from(50));
+	assert_eq!(b.cost(&[0u8; 0]), U256::from(10));
+	assert_eq!(b.cost(&[0u8; 1]), U256::from(30));
+	assert_eq!(b.cost(&[0u8; 32]), U256::from(30));
+	assert_eq!(b.cost(&[0u8; 33]), U256::from(50));
 
 	let i = [0u8, 1, 2, 3];
 	let mut o = [255u8; 4];
-	(*b.execute)(&i[..], &mut o[..]);
+	(*b.execute)(&i[..], &mut o[..]).unwrap();
 	assert_eq!(i, o);
 }
+
+#[test]
+fn unknown_from_registry() {
+	let registry = BuiltinRegistry::default();
+	let res = registry.builtin(ethjson::spec::Builtin {
+		name: "dw".to_owned(),
+		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}),
+		activate_at: None,
+	});
+
+	assert_eq!(res.unwrap_err(), SpecError::UnknownBuiltin("dw".to_owned()));
+}
+
+#[test]
+fn register_custom() {
+	let mut registry = BuiltinRegistry::new();
+	assert!(!registry.contains("identity"));
+	registry.register("answer", || Box::new(|_: &[u8], output: &mut [u8]| {
+		for o in output.iter_mut() {
+			*o = 42;
+		}
+		Ok(())
+	}));
+
+	let b = registry.builtin(ethjson::spec::Builtin {
+		name: "answer".to_owned(),
+		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}),
+		activate_at: None,
+	}).unwrap();
+
+	let mut o = [0u8; 4];
+	b.execute(&[], &mut o[..]).unwrap();
+	assert_eq!([42u8; 4], o);
+}
+
+#[test]
+fn arg_sizes_do_not_depend_on_pricing() {
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "zkSNARK".to_owned(),
+		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}),
+		activate_at: None,
+	});
+	assert_eq!(b.arg_sizes(&abi_encode_bytes(&[&[0u8; 64], &[0u8; 33], &[0u8; 1]])), vec![64, 33, 1]);
+
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "identity".to_owned(),
+		pricing: ethjson::spec::Pricing::AbiBytes(ethjson::spec::AbiBytes {
+			base: 1000,
+			words: vec![100, 10, 1],
+		}),
+		activate_at: None,
+	});
+	let i = abi_encode_bytes(&[&[0u8; 64], &[0u8; 33], &[0u8; 1]]);
+	assert_eq!(b.arg_sizes(&i), vec![i.len()]);
+}
+
+#[test]
+fn zksnark_malformed_input() {
+	let f = new_builtin_exec("zkSNARK");
+
+	let mut o = [255u8; 32];
+	assert_eq!(f(&[0u8; 2], &mut o[..]), Err(BuiltinError("input too short")));
+	assert_eq!(o, [0u8; 32]);
+
+	let mut o = [255u8; 32];
+	assert_eq!(f(&[0u8; 36], &mut o[..]), Err(BuiltinError("invalid ABI encoding")));
+	assert_eq!(o, [0u8; 32]);
+}
+
+#[cfg(test)]
+fn abi_encode_bytes(args: &[&[u8]]) -> Bytes {
+	let mut input = vec![0u8; 4];
+	input.extend(ethabi::Encoder::encode(args.iter().map(|a| Token::Bytes(a.to_vec())).collect()));
+	input
+}
+
+#[test]
+fn from_json_abi_bytes() {
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "zkSNARK".to_owned(),
+		pricing: ethjson::spec::Pricing::AbiBytes(ethjson::spec::AbiBytes {
+			base: 1000,
+			words: vec![100, 10, 1],
+		}),
+		activate_at: None,
+	});
+
+	assert_eq!(b.cost(&[]), U256::from(1000));
+	assert_eq!(b.cost(&abi_encode_bytes(&[&[], &[], &[]])), U256::from(1000));
+	assert_eq!(b.cost(&abi_encode_bytes(&[&[0u8; 64], &[0u8; 33], &[0u8; 1]])), U256::from(1000 + 200 + 20 + 1));
+}
+
+#[test]
+fn from_json_public_inputs() {
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "zkSNARK".to_owned(),
+		pricing: ethjson::spec::Pricing::PublicInputs(ethjson::spec::PublicInputs {
+			base: 1000,
+			input: 50,
+		}),
+		activate_at: None,
+	});
+
+	assert_eq!(b.cost(&[]), U256::from(1000));
+	assert_eq!(b.cost(&abi_encode_bytes(&[&[0u8; 1024], &[0u8; 256], &[]])), U256::from(1000));
+	assert_eq!(b.cost(&abi_encode_bytes(&[&[0u8; 1024], &[0u8; 256], &[0u8; 96]])), U256::from(1000 + 150));
+
+	assert_eq!(b.name, "zkSNARK");
+	assert_eq!(b.arg_sizes(&abi_encode_bytes(&[&[0u8; 1024], &[0u8; 256], &[0u8; 96]])), vec![1024, 256, 96]);
+	assert_eq!(b.arg_sizes(&[]), Vec::<usize>::new());
+}
+
+#[test]
+fn alt_bn128_add() {
+	use rustc_serialize::hex::FromHex;
+	let f = new_builtin_exec("alt_bn128_add");
+
+	let i = FromHex::from_hex("\
+		18b18acfb4c2c30276db5411368e7185b311dd124691610c5d3b74034e093dc9\
+		063c909c4720840cb5134cb9f59fa749755796819658d32efc0d288198f37266\
+		07c2b7f58a84bd6145f00c9c2bc0bb1a187f20ff2c92963a88019e7c6a014eed\
+		06614e20c147e940f2d70da3f74c9a17df361706a4485c742bd6788478fa17d7").unwrap();
+	let mut o = [255u8; 64];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("\
+		2243525c5efd4b9c3d3c45ac0ca3fe4dd85e830a4ce6b65fa1eeaee202839703\
+		301d1d33be6da8e509df21cc35964723180eed7532537db9ae5e7d48f195c915").unwrap())[..]);
+
+	// G1 + G1, with G1 = (1, 2)
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000002\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000002").unwrap();
+	let mut o = [255u8; 64];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("\
+		030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+		15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4").unwrap())[..]);
+
+	// points at infinity, and empty input which is zero-extended
+	let mut o = [255u8; 64];
+	f(&[0u8; 128], &mut o[..]).unwrap();
+	assert_eq!(o[..], [0u8; 64][..]);
+
+	let mut o = [255u8; 64];
+	f(&[], &mut o[..]).unwrap();
+	assert_eq!(o[..], [0u8; 64][..]);
+
+	// point not on curve
+	let i = FromHex::from_hex("\
+		1111111111111111111111111111111111111111111111111111111111111111\
+		1111111111111111111111111111111111111111111111111111111111111111\
+		1111111111111111111111111111111111111111111111111111111111111111\
+		1111111111111111111111111111111111111111111111111111111111111111").unwrap();
+	let mut o = [255u8; 64];
+	assert_eq!(f(&i[..], &mut o[..]), Err(BuiltinError("invalid curve point")));
+}
+
+#[test]
+fn alt_bn128_mul() {
+	use rustc_serialize::hex::FromHex;
+	let f = new_builtin_exec("alt_bn128_mul");
+
+	let i = FromHex::from_hex("\
+		2bd3e6d0f3b142924f5ca7b49ce5b9d54c4703d7ae5648e61d02268b1a0a9fb7\
+		21611ce0a6af85915e2f1d70300909ce2e49dfad4a4619c8390cae66cefdb204\
+		00000000000000000000000000000000000000000000000011138ce750fa15c2").unwrap();
+	let mut o = [255u8; 64];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("\
+		070a8d6a982153cae4be29d434e8faef8a47b274a053f5a4ee2a6c9c13c31e5c\
+		031b8ce914eba3a9ffb989f9cdd5b0f01943074bf4f0f315690ec3cec6981afc").unwrap())[..]);
+
+	// 2 * G1, with G1 = (1, 2)
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000002\
+		0000000000000000000000000000000000000000000000000000000000000002").unwrap();
+	let mut o = [255u8; 64];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("\
+		030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+		15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4").unwrap())[..]);
+
+	// (2^256 - 1) * G1; scalars not below the group order are accepted
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000002\
+		ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap();
+	let mut o = [255u8; 64];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("\
+		2f588cffe99db877a4434b598ab28f81e0522910ea52b45f0adaa772b2d5d352\
+		12f42fa8fd34fb1b33d8c6a718b6590198389b26fc9d8808d971f8b009777a97").unwrap())[..]);
+
+	// point at infinity
+	let mut o = [255u8; 64];
+	f(&[0u8; 96], &mut o[..]).unwrap();
+	assert_eq!(o[..], [0u8; 64][..]);
+
+	// point not on curve
+	let i = FromHex::from_hex("\
+		1111111111111111111111111111111111111111111111111111111111111111\
+		1111111111111111111111111111111111111111111111111111111111111111\
+		0f00000000000000000000000000000000000000000000000000000000000000").unwrap();
+	let mut o = [255u8; 64];
+	assert_eq!(f(&i[..], &mut o[..]), Err(BuiltinError("invalid curve point")));
+}
+
+#[test]
+fn alt_bn128_pairing() {
+	use rustc_serialize::hex::FromHex;
+	let f = new_builtin_exec("alt_bn128_pairing");
+
+	let g1 = "\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000002";
+	let minus_g1 = "\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45";
+	let g2 = "\
+		198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+		1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+		090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+		12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa";
+
+	// e(G1, G2) * e(-G1, G2) == 1
+	let i = FromHex::from_hex(&format!("{}{}{}{}", g1, g2, minus_g1, g2)).unwrap();
+	let mut o = [255u8; 32];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("0000000000000000000000000000000000000000000000000000000000000001").unwrap())[..]);
+
+	// e(G1, G2) != 1
+	let i = FromHex::from_hex(&format!("{}{}", g1, g2)).unwrap();
+	let mut o = [255u8; 32];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("0000000000000000000000000000000000000000000000000000000000000000").unwrap())[..]);
+
+	// empty product is 1
+	let mut o = [255u8; 32];
+	f(&[], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("0000000000000000000000000000000000000000000000000000000000000001").unwrap())[..]);
+
+	// invalid input length
+	let mut o = [255u8; 32];
+	assert_eq!(f(&[0u8; 191], &mut o[..]), Err(BuiltinError("input length must be a multiple of 192")));
+
+	// point not on curve
+	let not_on_curve: String = ::std::iter::repeat("11").take(64).collect();
+	let i = FromHex::from_hex(&format!("{}{}", not_on_curve, g2)).unwrap();
+	let mut o = [255u8; 32];
+	assert_eq!(f(&i[..], &mut o[..]), Err(BuiltinError("invalid curve point")));
+}
+
+#[test]
+fn from_json_alt_bn128_pairing() {
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "alt_bn128_pairing".to_owned(),
+		pricing: ethjson::spec::Pricing::AltBn128Pairing(ethjson::spec::AltBn128Pairing {
+			base: 100000,
+			pair: 80000,
+		}),
+		activate_at: None,
+	});
+
+	assert_eq!(b.cost(&[]), U256::from(100000));
+	assert_eq!(b.cost(&[0u8; 192]), U256::from(180000));
+	assert_eq!(b.cost(&[0u8; 384]), U256::from(260000));
+}
+
+#[test]
+fn modexp() {
+	use rustc_serialize::hex::FromHex;
+	let f = new_builtin_exec("modexp");
+
+	// Fermat's little theorem: 3^(p-1) % p == 1
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000020\
+		0000000000000000000000000000000000000000000000000000000000000020\
+		03\
+		fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+		fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f").unwrap();
+	let mut o = [255u8; 32];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &(FromHex::from_hex("0000000000000000000000000000000000000000000000000000000000000001").unwrap())[..]);
+
+	// empty base is zero
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000000\
+		0000000000000000000000000000000000000000000000000000000000000020\
+		0000000000000000000000000000000000000000000000000000000000000020\
+		fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+		fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f").unwrap();
+	let mut o = [255u8; 32];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(&o[..], &[0u8; 32][..]);
+
+	// short input is zero-extended: 2^0 % 5
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		02\
+		00\
+		05").unwrap();
+	let mut o = [255u8; 1];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(o, [1u8]);
+
+	// 2^10 % 1000 == 24, padded to the modulus length
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000002\
+		02\
+		0a\
+		03e8").unwrap();
+	let mut o = [255u8; 2];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(o, [0u8, 24]);
+
+	// empty modulus leaves output untouched
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000000\
+		02\
+		0a").unwrap();
+	let mut o = [255u8; 2];
+	f(&i[..], &mut o[..]).unwrap();
+	assert_eq!(o, [255u8; 2]);
+}
+
+#[test]
+fn from_json_modexp() {
+	use rustc_serialize::hex::FromHex;
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "modexp".to_owned(),
+		pricing: ethjson::spec::Pricing::Modexp(ethjson::spec::Modexp {
+			divisor: 20,
+		}),
+		activate_at: None,
+	});
+
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000020\
+		0000000000000000000000000000000000000000000000000000000000000020\
+		03\
+		fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+		fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f").unwrap();
+	assert_eq!(b.cost(&i), U256::from(13056));
+	assert_eq!(b.arg_sizes(&i), vec![1, 32, 32]);
+
+	// empty base and modulus are free
+	assert_eq!(b.cost(&[]), U256::zero());
+
+	// absurdly long modulus is unaffordable
+	let i = FromHex::from_hex("\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		0000000000000000000000000000000000000000000000000000000000000001\
+		ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").unwrap();
+	assert_eq!(b.cost(&i), !U256::zero());
+}
+
+#[test]
+fn from_json_activate_at() {
+	let b = Builtin::from(ethjson::spec::Builtin {
+		name: "identity".to_owned(),
+		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}),
+		activate_at: Some(ethjson::uint::Uint(100.into())),
+	});
+
+	assert!(!b.is_active(0));
+	assert!(!b.is_active(99));
+	assert!(b.is_active(100));
+	assert!(b.is_active(101));
+}
+
+#[test]
+fn verification_cache() {
+	use std::cell::Cell;
+	let cache = VerificationCache::default();
+	let calls = Cell::new(0);
+	let verify = |input: &[u8]| {
+		calls.set(calls.get() + 1);
+		Ok(input.len() == 1)
+	};
+
+	assert_eq!(cache.get_or_verify(&[0u8], &verify), Ok(true));
+	assert_eq!(cache.get_or_verify(&[0u8], &verify), Ok(true));
+	assert_eq!(calls.get(), 1);
+	assert_eq!(cache.get_or_verify(&[0u8, 1], &verify), Ok(false));
+	assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn zksnark_is_preverified() {
+	let registry = BuiltinRegistry::default();
+	let b = registry.builtin(ethjson::spec::Builtin {
+		name: "zkSNARK".to_owned(),
+		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}),
+		activate_at: None,
+	}).unwrap();
+	assert!(b.preverify);
+
+	let b = registry.builtin(ethjson::spec::Builtin {
+		name: "identity".to_owned(),
+		pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}),
+		activate_at: None,
+	}).unwrap();
+	assert!(!b.preverify);
+}
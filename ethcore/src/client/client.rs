@@ -35,7 +35,7 @@ use util::kvdb::*;
 
 // other
 use views::BlockView;
-use error::{ImportError, ExecutionError, BlockError, ImportResult};
+use error::{Error, ImportError, ExecutionError, CallError, ListError, BlockError, ImportResult};
 use header::BlockNumber;
 use state::State;
 use spec::Spec;
@@ -50,6 +50,8 @@ use block::*;
 use transaction::{LocalizedTransaction, SignedTransaction, Action};
 use blockchain::extras::TransactionAddress;
 use types::filter::Filter;
+use types::state_override::StateOverride;
+use types::account_proof::AccountProof;
 use log_entry::LocalizedLogEntry;
 use block_queue::{BlockQueue, BlockQueueInfo};
 use blockchain::{BlockChain, BlockProvider, TreeRoute, ImportRoute};
@@ -62,9 +64,10 @@ use executive::{Executive, Executed, TransactOptions, contract_address};
 use receipt::LocalizedReceipt;
 use trace::{TraceDB, ImportRequest as TraceImportRequest, LocalizedTrace, Database as TraceDatabase};
 use trace;
+use trace::struct_log::{StructLogOptions, StructLogTrace};
 use evm::Factory as EvmFactory;
-use miner::{Miner, MinerService};
-use util::TrieFactory;
+use miner::{Miner, MinerService, LocalTransactionsStore};
+use util::{TrieFactory, TrieError};
 
 // re-export
 pub use types::blockchain_info::BlockChainInfo;
@@ -194,7 +197,7 @@ impl Client {
 			state_db_config
 		);
 
-		if state_db.is_empty() && spec.ensure_db_good(state_db.as_hashdb_mut()) {
+		if state_db.is_empty() && spec.ensure_db_good_with(state_db.as_hashdb_mut(), &TrieFactory::new(config.trie_spec.clone())) {
 			state_db.commit(0, &spec.genesis_header().hash(), None).expect("Error commiting genesis state to state DB");
 		}
 
@@ -231,7 +234,10 @@ impl Client {
 			queue_transactions: AtomicUsize::new(0),
 			last_hashes: RwLock::new(VecDeque::new()),
 		};
-		Ok(Arc::new(client))
+		let client = Arc::new(client);
+		let local_transactions = try!(LocalTransactionsStore::open(Path::new(&append_path(&path, "local_transactions"))).map_err(ClientError::Database));
+		client.miner.set_local_transactions_store(&*client, local_transactions);
+		Ok(client)
 	}
 
 	/// Adds an actor to be notified on certain events
@@ -255,6 +261,60 @@ impl Client {
 		}
 	}
 
+	/// Returns the parent state of `block` with the first `count` transactions of the block applied,
+	/// together with the matching environment and all transactions of the block.
+	fn replay_prefix(&self, block: BlockID, count: usize) -> Result<(State, EnvInfo, Vec<SignedTransaction>), CallError> {
+		let bytes = try!(self.block(block).ok_or(CallError::BlockNotFound));
+		let view = BlockView::new(&bytes);
+		let header = view.header();
+		let transactions = view.transactions();
+		if count > transactions.len() {
+			return Err(CallError::TransactionNotFound);
+		}
+
+		let parent = try!(self.chain.block_header(header.parent_hash()).ok_or(CallError::BlockNotFound));
+
+		// open the block just like enactment does, so that the engine's block transitions are applied
+		let mut open_block = try!(OpenBlock::new(
+			self.engine.deref().deref(),
+			&self.vm_factory,
+			self.trie_factory.clone(),
+			false,
+			self.state_db.lock().boxed_clone(),
+			&parent,
+			self.build_last_hashes(parent.hash()),
+			header.author().clone(),
+			(*header.gas_limit(), *header.gas_limit()),
+			vec![],
+		).map_err(|e| match e {
+			Error::Trie(TrieError::InvalidStateRoot) => CallError::StatePruned,
+			_ => CallError::StateCorrupt,
+		}));
+		open_block.set_difficulty(*header.difficulty());
+		open_block.set_gas_limit(*header.gas_limit());
+		open_block.set_timestamp(header.timestamp());
+		let mut env_info = open_block.env_info();
+		let mut state = open_block.state().clone();
+
+		for t in &transactions[..count] {
+			let options = TransactOptions { tracing: false, vm_tracing: false, check_nonce: true };
+			let executed = try!(Executive::new(&mut state, &env_info, self.engine.deref().deref(), &self.vm_factory).transact(t, options));
+			env_info.gas_used = env_info.gas_used + executed.gas_used;
+		}
+		Ok((state, env_info, transactions))
+	}
+
+	/// Executes a mined transaction `t` on top of `state`, running given analytics.
+	fn replay_with_analytics(&self, state: &mut State, env_info: &EnvInfo, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, CallError> {
+		let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
+		let options = TransactOptions { tracing: analytics.transaction_tracing, vm_tracing: analytics.vm_tracing, check_nonce: true };
+		let mut executed = try!(Executive::new(state, env_info, self.engine.deref().deref(), &self.vm_factory).transact(t, options));
+		if let Some(original) = original_state {
+			executed.state_diff = Some(state.diff_from(original));
+		}
+		Ok(executed)
+	}
+
 	fn build_last_hashes(&self, parent_hash: H256) -> LastHashes {
 		{
 			let hashes = self.last_hashes.read();
@@ -634,8 +694,8 @@ impl Client {
 }
 
 impl BlockChainClient for Client {
-	fn call(&self, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, ExecutionError> {
-		let header = self.block_header(BlockID::Latest).unwrap();
+	fn call(&self, t: &SignedTransaction, block: BlockID, overrides: &StateOverride, analytics: CallAnalytics) -> Result<Executed, CallError> {
+		let header = try!(self.block_header(block.clone()).ok_or(CallError::BlockNotFound));
 		let view = HeaderView::new(&header);
 		let last_hashes = self.build_last_hashes(view.hash());
 		let env_info = EnvInfo {
@@ -648,7 +708,10 @@ impl BlockChainClient for Client {
 			gas_limit: U256::max_value(),
 		};
 		// that's just a copy of the state.
-		let mut state = self.state();
+		let mut state = try!(self.state_at(block.clone()).ok_or(CallError::StatePruned));
+		state.apply_overrides(overrides);
+		let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
+
 		let sender = try!(t.sender().map_err(|e| {
 			let message = format!("Transaction malformed: {:?}", e);
 			ExecutionError::TransactionMalformed(message)
@@ -663,12 +726,43 @@ impl BlockChainClient for Client {
 		let mut ret = Executive::new(&mut state, &env_info, self.engine.deref().deref(), &self.vm_factory).transact(t, options);
 
 		// TODO gav move this into Executive.
-		if analytics.state_diffing {
+		if let Some(original) = original_state {
 			if let Ok(ref mut x) = ret {
-				x.state_diff = Some(state.diff_from(self.state()));
+				x.state_diff = Some(state.diff_from(original));
 			}
 		}
-		ret
+		ret.map_err(Into::into)
+	}
+
+	fn replay(&self, id: TransactionID, analytics: CallAnalytics) -> Result<Executed, CallError> {
+		let address = try!(self.transaction_address(id).ok_or(CallError::TransactionNotFound));
+		let (mut state, env_info, transactions) = try!(self.replay_prefix(BlockID::Hash(address.block_hash), address.index));
+		let t = try!(transactions.get(address.index).ok_or(CallError::TransactionNotFound));
+		self.replay_with_analytics(&mut state, &env_info, t, analytics)
+	}
+
+	fn replay_block_transactions(&self, block: BlockID, analytics: CallAnalytics) -> Result<Vec<Executed>, CallError> {
+		let (mut state, mut env_info, transactions) = try!(self.replay_prefix(block, 0));
+		let mut results = Vec::with_capacity(transactions.len());
+		for t in &transactions {
+			let executed = try!(self.replay_with_analytics(&mut state, &env_info, t, analytics));
+			env_info.gas_used = env_info.gas_used + executed.gas_used;
+			results.push(executed);
+		}
+		Ok(results)
+	}
+
+	fn replay_struct_logs(&self, id: TransactionID, options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		let address = try!(self.transaction_address(id).ok_or(CallError::TransactionNotFound));
+		let (mut state, env_info, transactions) = try!(self.replay_prefix(BlockID::Hash(address.block_hash), address.index));
+		let t = try!(transactions.get(address.index).ok_or(CallError::TransactionNotFound));
+		let (executed, logs) = try!(Executive::new(&mut state, &env_info, self.engine.deref().deref(), &self.vm_factory)
+			.transact_with_vm_tracer_output(t, true, trace::NoopTracer, trace::StructLogTracer::new(options)));
+		Ok(StructLogTrace {
+			gas_used: executed.gas_used,
+			return_value: executed.output,
+			struct_logs: logs.unwrap_or_else(Vec::new),
+		})
 	}
 
 	fn keep_alive(&self) {
@@ -742,6 +836,24 @@ impl BlockChainClient for Client {
 		self.state_at(id).map(|s| s.storage_at(address, position))
 	}
 
+	fn prove_account(&self, address: &Address, storage_keys: Vec<H256>, id: BlockID) -> Option<AccountProof> {
+		self.state_at(id).map(|s| s.prove_account(address, &storage_keys))
+	}
+
+	fn list_accounts(&self, id: BlockID, after: Option<Address>, count: u64) -> Result<Vec<Address>, ListError> {
+		if !self.trie_factory.is_fat() {
+			return Err(ListError::Unavailable);
+		}
+		self.state_at(id).ok_or(ListError::Unavailable).and_then(|s| s.list_accounts(after.as_ref(), count as usize))
+	}
+
+	fn list_storage(&self, id: BlockID, account: &Address, after: Option<H256>, count: u64) -> Result<Vec<H256>, ListError> {
+		if !self.trie_factory.is_fat() {
+			return Err(ListError::Unavailable);
+		}
+		self.state_at(id).ok_or(ListError::Unavailable).and_then(|s| s.list_storage(account, after.as_ref(), count as usize))
+	}
+
 	fn transaction(&self, id: TransactionID) -> Option<LocalizedTransaction> {
 		self.transaction_address(id).and_then(|address| self.chain.transaction(&address))
 	}
@@ -846,6 +958,10 @@ impl BlockChainClient for Client {
 		}
 	}
 
+	fn signing_chain_id(&self) -> Option<u64> {
+		self.engine.signing_chain_id(self.chain.best_block_number() + 1)
+	}
+
 	fn blocks_with_bloom(&self, bloom: &H2048, from_block: BlockID, to_block: BlockID) -> Option<Vec<BlockNumber>> {
 		match (self.block_number(from_block), self.block_number(to_block)) {
 			(Some(from), Some(to)) => Some(self.chain.blocks_with_bloom(bloom, from, to)),
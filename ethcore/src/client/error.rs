@@ -6,6 +6,8 @@ use std::fmt::{Display, Formatter, Error as FmtError};
 pub enum Error {
 	/// TraceDB configuration error.
 	Trace(TraceError),
+	/// Database error.
+	Database(String),
 }
 
 impl From<TraceError> for Error {
@@ -17,7 +19,8 @@ impl From<TraceError> for Error {
 impl Display for Error {
 	fn fmt(&self, f: &mut Formatter) -> Result<(), FmtError> {
 		match *self {
-			Error::Trace(ref err) => write!(f, "{}", err)
+			Error::Trace(ref err) => write!(f, "{}", err),
+			Error::Database(ref err) => write!(f, "Database error: {}", err),
 		}
 	}
 }
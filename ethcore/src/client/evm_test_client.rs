@@ -0,0 +1,110 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Client executing single transactions against a given state; used by `evmbin`.
+
+use std::env;
+use util::*;
+use spec::Spec;
+use state::State;
+use pod_state::{self, PodState};
+use transaction::SignedTransaction;
+use executive::{Executive, Executed, TransactOptions};
+use error::ExecutionError;
+use types::state_diff::StateDiff;
+use evm::{Factory as EvmFactory, VMType};
+use env_info::EnvInfo;
+
+/// Executes transactions against a `PodState` using the builtins and schedule of a chain spec.
+/// Nothing is persisted; every transaction runs on a fresh temporary database which is removed afterwards.
+pub struct EvmTestClient {
+	spec: Spec,
+	vm_factory: EvmFactory,
+}
+
+impl EvmTestClient {
+	/// Create a new client for chain `spec` using given VM type.
+	pub fn new(spec: Spec, vm_type: VMType) -> Self {
+		EvmTestClient {
+			spec: spec,
+			vm_factory: EvmFactory::new(vm_type),
+		}
+	}
+
+	/// Chain spec of this client.
+	pub fn spec(&self) -> &Spec {
+		&self.spec
+	}
+
+	/// Execute transaction `t` in the block described by `env_info` on top of state `pre`.
+	/// Returns the execution result together with the difference between `pre` and the post-state.
+	/// The transaction nonce is not checked.
+	pub fn transact(&self, env_info: &EnvInfo, pre: PodState, t: &SignedTransaction, tracing: bool) -> Result<(Executed, StateDiff), ExecutionError> {
+		let mut dir = env::temp_dir();
+		dir.push(H32::random().hex());
+		let result = {
+			let db = journaldb::new(dir.to_str().expect("temp dir path is valid unicode"), journaldb::Algorithm::Archive, DatabaseConfig::default());
+			let mut state = State::new(db, self.spec.engine.account_start_nonce(), Default::default());
+			state.populate_from(pre.clone());
+
+			let options = TransactOptions { tracing: tracing, vm_tracing: false, check_nonce: false };
+			Executive::new(&mut state, env_info, self.spec.engine.deref(), &self.vm_factory).transact(t, options)
+				.map(|executed| (executed, pod_state::diff_pod(&pre, &state.to_pod())))
+		};
+		let _ = fs::remove_dir_all(&dir);
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use util::*;
+	use spec::Spec;
+	use pod_account::PodAccount;
+	use pod_state::PodState;
+	use transaction::{Transaction, Action};
+	use evm::VMType;
+	use env_info::EnvInfo;
+	use super::EvmTestClient;
+
+	#[test]
+	fn should_call_builtin_on_pod_state() {
+		let sender = Address::from(0x10);
+		let mut accounts = BTreeMap::new();
+		accounts.insert(sender.clone(), PodAccount {
+			balance: 100_000.into(),
+			nonce: 0x100000.into(),
+			code: vec![],
+			storage: BTreeMap::new(),
+		});
+		let t = Transaction {
+			nonce: 0x100000.into(),
+			gas_price: 1.into(),
+			gas: 100_000.into(),
+			action: Action::Call(0x4.into()),
+			value: 0.into(),
+			data: vec![1, 2, 3],
+		}.fake_sign(sender.clone());
+		let mut env_info = EnvInfo::default();
+		env_info.gas_limit = 100_000.into();
+
+		let client = EvmTestClient::new(Spec::new_test(), VMType::Interpreter);
+		let (executed, diff) = client.transact(&env_info, PodState::from(accounts), &t, false).unwrap();
+
+		assert_eq!(executed.gas_used, U256::from(21_000 + 3 * 68 + 18));
+		assert!(diff.get().contains_key(&sender));
+	}
+}
@@ -21,18 +21,23 @@ mod error;
 mod test_client;
 mod trace;
 mod client;
+mod evm_test_client;
 
 pub use self::client::*;
 pub use self::config::{Mode, ClientConfig, DatabaseCompactionProfile, BlockQueueConfig, BlockChainConfig, Switch, VMType};
 pub use self::error::Error;
 pub use types::ids::*;
 pub use self::test_client::{TestBlockChainClient, EachBlockWith};
+pub use self::evm_test_client::EvmTestClient;
 pub use types::trace_filter::Filter as TraceFilter;
 pub use executive::{Executed, Executive, TransactOptions};
 pub use env_info::{LastHashes, EnvInfo};
 pub use self::chain_notify::{ChainNotify, ChainNotifyClient};
 
 pub use types::call_analytics::CallAnalytics;
+pub use types::trace_types::struct_log::{StructLog, StructLogOptions, StructLogTrace};
+pub use types::state_override::{StateOverride, AccountOverride};
+pub use types::account_proof::{AccountProof, StorageProof};
 pub use block_import_error::BlockImportError;
 pub use transaction_import::TransactionImportResult;
 pub use transaction_import::TransactionImportError;
@@ -23,7 +23,7 @@ use transaction::{Transaction, LocalizedTransaction, SignedTransaction, Action};
 use blockchain::TreeRoute;
 use client::{BlockChainClient, MiningBlockChainClient, BlockChainInfo, BlockStatus, BlockID,
 	TransactionID, UncleID, TraceId, TraceFilter, LastHashes, CallAnalytics,
-	BlockImportError};
+	BlockImportError, StructLog, StructLogOptions, StructLogTrace};
 use header::{Header as BlockHeader, BlockNumber};
 use filter::Filter;
 use log_entry::LocalizedLogEntry;
@@ -37,7 +37,9 @@ use spec::Spec;
 use block_queue::BlockQueueInfo;
 use block::{OpenBlock, SealedBlock};
 use executive::Executed;
-use error::ExecutionError;
+use error::{CallError, ExecutionError, ListError};
+use types::state_override::StateOverride;
+use types::account_proof::{AccountProof, StorageProof};
 use trace::LocalizedTrace;
 
 /// Test client.
@@ -62,6 +64,8 @@ pub struct TestBlockChainClient {
 	pub code: RwLock<HashMap<Address, Bytes>>,
 	/// Execution result.
 	pub execution_result: RwLock<Option<Executed>>,
+	/// Struct logs returned for replayed transactions.
+	pub struct_logs: RwLock<Vec<StructLog>>,
 	/// Transaction receipts.
 	pub receipts: RwLock<HashMap<TransactionID, LocalizedReceipt>>,
 	/// Block queue size.
@@ -108,6 +112,7 @@ impl TestBlockChainClient {
 			storage: RwLock::new(HashMap::new()),
 			code: RwLock::new(HashMap::new()),
 			execution_result: RwLock::new(None),
+			struct_logs: RwLock::new(vec![]),
 			receipts: RwLock::new(HashMap::new()),
 			queue_size: AtomicUsize::new(0),
 			miner: Arc::new(Miner::with_spec(Spec::new_test())),
@@ -255,6 +260,14 @@ pub fn get_temp_journal_db() -> GuardedTempResult<Box<JournalDB>> {
 	}
 }
 
+fn paginate<T: PartialEq>(items: Vec<T>, after: Option<T>, count: u64) -> Vec<T> {
+	let skip = match after {
+		Some(after) => items.iter().position(|i| i == &after).map_or(items.len(), |p| p + 1),
+		None => 0,
+	};
+	items.into_iter().skip(skip).take(count as usize).collect()
+}
+
 impl MiningBlockChainClient for TestBlockChainClient {
 	fn prepare_open_block(&self, _author: Address, _gas_range_target: (U256, U256), _extra_data: Bytes) -> OpenBlock {
 		let engine = &self.spec.engine;
@@ -288,10 +301,42 @@ impl MiningBlockChainClient for TestBlockChainClient {
 }
 
 impl BlockChainClient for TestBlockChainClient {
-	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics) -> Result<Executed, ExecutionError> {
+	fn call(&self, _t: &SignedTransaction, block: BlockID, _overrides: &StateOverride, _analytics: CallAnalytics) -> Result<Executed, CallError> {
+		let hash = match Self::block_hash(self, block) {
+			Some(hash) if self.blocks.read().contains_key(&hash) => hash,
+			_ => return Err(CallError::BlockNotFound),
+		};
+		// only the state of the latest block is kept
+		if hash != *self.last_hash.read() {
+			return Err(CallError::StatePruned);
+		}
 		Ok(self.execution_result.read().clone().unwrap())
 	}
 
+	fn replay(&self, id: TransactionID, _analytics: CallAnalytics) -> Result<Executed, CallError> {
+		// only transactions with a known receipt can be replayed; without an execution result set the replay fails
+		match self.receipts.read().get(&id) {
+			Some(_) => self.execution_result.read().clone().ok_or(CallError::Execution(ExecutionError::Internal)),
+			None => Err(CallError::TransactionNotFound),
+		}
+	}
+
+	fn replay_block_transactions(&self, block: BlockID, _analytics: CallAnalytics) -> Result<Vec<Executed>, CallError> {
+		match Self::block_hash(self, block) {
+			Some(_) => Ok(vec![self.execution_result.read().clone().unwrap()]),
+			None => Err(CallError::BlockNotFound),
+		}
+	}
+
+	fn replay_struct_logs(&self, id: TransactionID, _options: StructLogOptions) -> Result<StructLogTrace, CallError> {
+		let executed = try!(self.replay(id, Default::default()));
+		Ok(StructLogTrace {
+			gas_used: executed.gas_used,
+			return_value: executed.output,
+			struct_logs: self.struct_logs.read().clone(),
+		})
+	}
+
 	fn block_total_difficulty(&self, _id: BlockID) -> Option<U256> {
 		Some(U256::zero())
 	}
@@ -335,6 +380,43 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 
+	fn prove_account(&self, address: &Address, storage_keys: Vec<H256>, id: BlockID) -> Option<AccountProof> {
+		// proofs are left empty; only the proven values are mocked.
+		self.balance(address, id).map(|balance| AccountProof {
+			address: address.clone(),
+			balance: balance,
+			nonce: self.nonces.read().get(address).cloned().unwrap_or_else(U256::zero),
+			code_hash: self.code.read().get(address).map_or(SHA3_EMPTY, |c| c.sha3()),
+			storage_hash: SHA3_NULL_RLP,
+			account_proof: vec![],
+			storage_proof: storage_keys.into_iter().map(|key| StorageProof {
+				value: self.storage.read().get(&(address.clone(), key.clone())).cloned().unwrap_or_else(H256::new),
+				key: key,
+				proof: vec![],
+			}).collect(),
+		})
+	}
+
+	fn list_accounts(&self, id: BlockID, after: Option<Address>, count: u64) -> Result<Vec<Address>, ListError> {
+		if let BlockID::Latest = id {
+			let mut accounts: Vec<_> = self.balances.read().keys().cloned().collect();
+			accounts.sort();
+			Ok(paginate(accounts, after, count))
+		} else {
+			Err(ListError::Unavailable)
+		}
+	}
+
+	fn list_storage(&self, id: BlockID, account: &Address, after: Option<H256>, count: u64) -> Result<Vec<H256>, ListError> {
+		if let BlockID::Latest = id {
+			let mut keys: Vec<_> = self.storage.read().keys().filter(|k| &k.0 == account).map(|k| k.1.clone()).collect();
+			keys.sort();
+			Ok(paginate(keys, after, count))
+		} else {
+			Err(ListError::Unavailable)
+		}
+	}
+
 	fn transaction(&self, _id: TransactionID) -> Option<LocalizedTransaction> {
 		unimplemented!();
 	}
@@ -512,6 +594,10 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 
+	fn signing_chain_id(&self) -> Option<u64> {
+		self.spec.engine.signing_chain_id(self.chain_info().best_block_number + 1)
+	}
+
 	fn filter_traces(&self, _filter: TraceFilter) -> Option<Vec<LocalizedTrace>> {
 		unimplemented!();
 	}
@@ -26,7 +26,7 @@ use transaction::{LocalizedTransaction, SignedTransaction};
 use log_entry::LocalizedLogEntry;
 use filter::Filter;
 use views::{BlockView};
-use error::{ImportResult, ExecutionError};
+use error::{ImportResult, CallError, ListError};
 use receipt::LocalizedReceipt;
 use trace::LocalizedTrace;
 use evm::Factory as EvmFactory;
@@ -35,6 +35,9 @@ use types::trace_filter::Filter as TraceFilter;
 use executive::Executed;
 use env_info::LastHashes;
 use types::call_analytics::CallAnalytics;
+use types::trace_types::struct_log::{StructLogOptions, StructLogTrace};
+use types::state_override::StateOverride;
+use types::account_proof::AccountProof;
 use block_import_error::BlockImportError;
 use std::mem;
 use std::collections::VecDeque;
@@ -103,6 +106,20 @@ pub trait BlockChainClient : Sync + Send {
 	/// Returns None if and only if the block's root hash has been pruned from the DB.
 	fn storage_at(&self, address: &Address, position: &H256, id: BlockID) -> Option<H256>;
 
+	/// Get Merkle proofs of an account and its storage slots `storage_keys` against the state root of block `id`.
+	/// Returns None if and only if the block's state is not available.
+	fn prove_account(&self, address: &Address, storage_keys: Vec<H256>, id: BlockID) -> Option<AccountProof>;
+
+	/// Get up to `count` accounts in the state of block `id`, starting after `after` if given.
+	/// Fails with `ListError::Unavailable` if the block's state is not available or the state DB doesn't keep
+	/// key preimages (`--fat-db`).
+	fn list_accounts(&self, id: BlockID, after: Option<Address>, count: u64) -> Result<Vec<Address>, ListError>;
+
+	/// Get up to `count` storage keys of `account` in the state of block `id`, starting after `after` if given.
+	/// Fails with `ListError::Unavailable` if the block's state is not available or the state DB doesn't keep
+	/// key preimages (`--fat-db`).
+	fn list_storage(&self, id: BlockID, account: &Address, after: Option<H256>, count: u64) -> Result<Vec<H256>, ListError>;
+
 	/// Get value of the storage at given position at the latest block's state.
 	fn latest_storage_at(&self, address: &Address, position: &H256) -> H256 {
 		self.storage_at(address, position, BlockID::Latest)
@@ -144,6 +161,9 @@ pub trait BlockChainClient : Sync + Send {
 	/// Get blockchain information.
 	fn chain_info(&self) -> BlockChainInfo;
 
+	/// Chain id new transactions should be signed for (EIP-155); None if replay protection is not active yet.
+	fn signing_chain_id(&self) -> Option<u64>;
+
 	/// Get the best block header.
 	fn best_block_header(&self) -> Bytes {
 		// TODO: lock blockchain only once
@@ -156,9 +176,20 @@ pub trait BlockChainClient : Sync + Send {
 	/// Returns logs matching given filter.
 	fn logs(&self, filter: Filter) -> Vec<LocalizedLogEntry>;
 
-	/// Makes a non-persistent transaction call.
-	// TODO: should be able to accept blockchain location for call.
-	fn call(&self, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, ExecutionError>;
+	/// Makes a non-persistent transaction call on top of the state of `block` modified by `overrides`.
+	/// Fails with `CallError::BlockNotFound` if `block` is not known and with `CallError::StatePruned`
+	/// if its state is no longer available.
+	fn call(&self, t: &SignedTransaction, block: BlockID, overrides: &StateOverride, analytics: CallAnalytics) -> Result<Executed, CallError>;
+
+	/// Re-executes a mined transaction on top of the parent state of its block, after replaying
+	/// the transactions preceding it. Requires the parent state to be available.
+	fn replay(&self, t: TransactionID, analytics: CallAnalytics) -> Result<Executed, CallError>;
+
+	/// Re-executes every transaction of `block` on top of its parent state.
+	fn replay_block_transactions(&self, block: BlockID, analytics: CallAnalytics) -> Result<Vec<Executed>, CallError>;
+
+	/// Re-executes a mined transaction like `replay`, recording geth-style struct logs.
+	fn replay_struct_logs(&self, t: TransactionID, options: StructLogOptions) -> Result<StructLogTrace, CallError>;
 
 	/// Returns traces matching given filter.
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>>;
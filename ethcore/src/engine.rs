@@ -20,8 +20,12 @@ use common::*;
 use account_provider::AccountProvider;
 use block::ExecutedBlock;
 use spec::CommonParams;
+use snark_registry::SnarkRegistry;
 use evm::Schedule;
 
+/// Maximum number of builtin calls of a single block run by `Engine::preverify_builtin_calls`.
+pub const MAX_PREVERIFIED_BUILTIN_CALLS: usize = 16;
+
 /// A consensus mechanism for the chain. Generally either proof-of-work or proof-of-stake-based.
 /// Provides hooks into each of the major parts of block import.
 pub trait Engine : Sync + Send {
@@ -54,6 +58,13 @@ pub trait Engine : Sync + Send {
 	fn maximum_uncle_age(&self) -> usize { 6 }
 	/// The nonce with which accounts begin.
 	fn account_start_nonce(&self) -> U256 { self.params().account_start_nonce }
+	/// The zkSNARK verification key registry, if the chain has one.
+	fn zksnark_registry(&self) -> Option<&SnarkRegistry> { self.params().zksnark_registry.as_ref() }
+	/// The chain id replay-protected (EIP-155) transactions must be signed for in block `number`;
+	/// None if replay protection is not active yet.
+	fn signing_chain_id(&self, number: BlockNumber) -> Option<u64> {
+		if number >= self.params().eip155_transition { Some(self.params().network_id.low_u64()) } else { None }
+	}
 
 	/// Block transformation functions, before the transactions.
 	fn on_new_block(&self, _block: &mut ExecutedBlock) {}
@@ -68,6 +79,10 @@ pub trait Engine : Sync + Send {
 	/// be returned.
 	fn generate_seal(&self, _block: &ExecutedBlock, _accounts: Option<&AccountProvider>) -> Option<Vec<Bytes>> { None }
 
+	/// Whether `generate_seal` always succeeds, so blocks should be sealed as soon as there are transactions
+	/// instead of waiting for the reseal period.
+	fn seals_instantly(&self) -> bool { false }
+
 	/// Phase 1 quick block verification. Only does checks that are cheap. `block` (the header's full block)
 	/// may be provided for additional checks. Returns either a null `Ok` or a general error detailing the problem with import.
 	fn verify_block_basic(&self, _header: &Header,  _block: Option<&[u8]>) -> Result<(), Error> { Ok(()) }
@@ -83,7 +98,9 @@ pub trait Engine : Sync + Send {
 	/// Additional verification for transactions in blocks.
 	// TODO: Add flags for which bits of the transaction to check.
 	// TODO: consider including State in the params.
-	fn verify_transaction_basic(&self, _t: &SignedTransaction, _header: &Header) -> Result<(), Error> { Ok(()) }
+	fn verify_transaction_basic(&self, t: &SignedTransaction, header: &Header) -> Result<(), Error> {
+		t.check_chain_id(self.signing_chain_id(header.number()))
+	}
 	/// Verify a particular transaction is valid.
 	fn verify_transaction(&self, _t: &SignedTransaction, _header: &Header) -> Result<(), Error> { Ok(()) }
 
@@ -104,14 +121,55 @@ pub trait Engine : Sync + Send {
 
 	// TODO: builtin contract routing - to do this properly, it will require removing the built-in configuration-reading logic
 	// from Spec into here and removing the Spec::builtins field.
-	/// Determine whether a particular address is a builtin contract.
-	fn is_builtin(&self, a: &Address) -> bool { self.builtins().contains_key(a) }
+	/// Determine whether a particular address is a builtin contract active in the block of `env_info`.
+	fn is_builtin(&self, a: &Address, env_info: &EnvInfo) -> bool {
+		self.builtins().get(a).map_or(false, |b| b.is_active(env_info.number))
+	}
 	/// Determine the code execution cost of the builtin contract with address `a`.
-	/// Panics if `is_builtin(a)` is not true.
-	fn cost_of_builtin(&self, a: &Address, input: &[u8]) -> U256 { self.builtins().get(a).unwrap().cost(input.len()) }
+	/// Panics if `is_builtin(a, env_info)` is not true.
+	fn cost_of_builtin(&self, a: &Address, env_info: &EnvInfo, input: &[u8]) -> U256 {
+		assert!(self.is_builtin(a, env_info), "builtin must be active");
+		self.builtins().get(a).unwrap().cost(input)
+	}
+	/// Run calls to builtins marked for preverification made directly by `transactions` of the
+	/// block with `header`, so that their results are cached by the time the block is enacted.
+	/// This is called from the block queue's verifier threads.
+	///
+	/// Only calls whose gas pays for the intrinsic and builtin cost are run, and no more than
+	/// `MAX_PREVERIFIED_BUILTIN_CALLS` of them, committing no more gas than the block's gas limit.
+	/// Anything else is left to enactment, where it is paid for.
+	fn preverify_builtin_calls(&self, header: &Header, transactions: &[SignedTransaction]) {
+		let schedule = self.schedule(&EnvInfo {
+			number: header.number(),
+			gas_limit: *header.gas_limit(),
+			..Default::default()
+		});
+		let mut gas_left = *header.gas_limit();
+		let mut calls_left = MAX_PREVERIFIED_BUILTIN_CALLS;
+		for t in transactions {
+			if calls_left == 0 || t.gas > gas_left {
+				break;
+			}
+			if let Action::Call(ref a) = t.action {
+				if let Some(b) = self.builtins().get(a) {
+					let is_paid = || U256::from(t.gas_required(&schedule)) + b.cost(&t.data) <= t.gas;
+					if b.preverify && b.is_active(header.number()) && is_paid() {
+						let mut output = [0u8; 32];
+						let _ = b.execute(&t.data, &mut output);
+						calls_left -= 1;
+					}
+				}
+			}
+			gas_left = gas_left - t.gas;
+		}
+	}
 	/// Execution the builtin contract `a` on `input` and return `output`.
-	/// Panics if `is_builtin(a)` is not true.
-	fn execute_builtin(&self, a: &Address, input: &[u8], output: &mut [u8]) { self.builtins().get(a).unwrap().execute(input, output); }
+	/// Returns an error if the builtin failed, in which case the call should fail.
+	/// Panics if `is_builtin(a, env_info)` is not true.
+	fn execute_builtin(&self, a: &Address, env_info: &EnvInfo, input: &[u8], output: &mut [u8]) -> BuiltinResult {
+		assert!(self.is_builtin(a, env_info), "builtin must be active");
+		self.builtins().get(a).unwrap().execute(input, output)
+	}
 
 	// TODO: sealing stuff - though might want to leave this for later.
 }
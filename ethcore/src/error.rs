@@ -22,7 +22,8 @@ use basic_types::LogBloom;
 use client::Error as ClientError;
 use ipc::binary::{BinaryConvertError, BinaryConvertable};
 use types::block_import_error::BlockImportError;
-pub use types::executed::ExecutionError;
+pub use types::executed::{ExecutionError, CallError};
+pub use types::list_error::ListError;
 
 #[derive(Debug, PartialEq, Clone)]
 /// Errors concerning transaction processing.
@@ -32,8 +33,13 @@ pub enum TransactionError {
 	/// Transaction is not valid anymore (state already has higher nonce)
 	Old,
 	/// Transaction has too low fee
-	/// (there is already a transaction with the same sender-nonce but higher gas price)
-	TooCheapToReplace,
+	/// (there is already a transaction with the same sender-nonce and the gas price is not bumped enough to replace it)
+	TooCheapToReplace {
+		/// Minimal gas price required to replace the queued transaction
+		minimal: U256,
+		/// Transaction gas price
+		got: U256,
+	},
 	/// Transaction was not imported to the queue because limit has been reached.
 	LimitReached,
 	/// Transaction's gas price is below threshold.
@@ -59,6 +65,13 @@ pub enum TransactionError {
 	},
 	/// Transaction's gas limit (aka gas) is invalid.
 	InvalidGasLimit(OutOfBounds<U256>),
+	/// Transaction is replay-protected for a different chain, or replay protection is not active yet.
+	InvalidChainId {
+		/// Chain id accepted at this block, if any
+		expected: Option<u64>,
+		/// Chain id of the transaction
+		got: u64,
+	},
 }
 
 impl fmt::Display for TransactionError {
@@ -67,7 +80,8 @@ impl fmt::Display for TransactionError {
 		let msg = match *self {
 			AlreadyImported => "Already imported".into(),
 			Old => "No longer valid".into(),
-			TooCheapToReplace => "Gas price too low to replace".into(),
+			TooCheapToReplace { minimal, got } =>
+				format!("Gas price too low to replace. Min={}, Given={}", minimal, got),
 			LimitReached => "Transaction limit reached".into(),
 			InsufficientGasPrice { minimal, got } =>
 				format!("Insufficient gas price. Min={}, Given={}", minimal, got),
@@ -77,6 +91,8 @@ impl fmt::Display for TransactionError {
 			GasLimitExceeded { limit, got } =>
 				format!("Gas limit exceeded. Limit={}, Given={}", limit, got),
 			InvalidGasLimit(ref err) => format!("Invalid gas limit. {}", err),
+			InvalidChainId { expected, got } =>
+				format!("Invalid chain id. Expected={:?}, Given={}", expected, got),
 		};
 
 		f.write_fmt(format_args!("Transaction error ({})", msg))
@@ -204,6 +220,24 @@ impl fmt::Display for ImportError {
 	}
 }
 
+#[derive(Debug, PartialEq, Clone)]
+/// Errors concerning chain specification loading.
+pub enum SpecError {
+	/// Spec could not be deserialized.
+	InvalidJson(String),
+	/// Spec references a builtin contract which has not been registered.
+	UnknownBuiltin(String),
+}
+
+impl fmt::Display for SpecError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SpecError::InvalidJson(ref err) => f.write_fmt(format_args!("Invalid chain spec ({})", err)),
+			SpecError::UnknownBuiltin(ref name) => f.write_fmt(format_args!("Unknown builtin name ({})", name)),
+		}
+	}
+}
+
 #[derive(Debug)]
 /// General error type which should be capable of representing all errors in ethcore.
 pub enum Error {
@@ -231,6 +265,8 @@ pub enum Error {
 	Io(::std::io::Error),
 	/// Snappy error.
 	Snappy(::util::snappy::InvalidInput),
+	/// Error concerning chain spec loading.
+	Spec(SpecError),
 }
 
 impl fmt::Display for Error {
@@ -249,6 +285,7 @@ impl fmt::Display for Error {
 			Error::Trie(ref err) => f.write_fmt(format_args!("{}", err)),
 			Error::Io(ref err) => f.write_fmt(format_args!("{}", err)),
 			Error::Snappy(ref err) => f.write_fmt(format_args!("{}", err)),
+			Error::Spec(ref err) => f.write_fmt(format_args!("{}", err)),
 		}
 	}
 }
@@ -280,6 +317,12 @@ impl From<BlockError> for Error {
 	}
 }
 
+impl From<SpecError> for Error {
+	fn from(err: SpecError) -> Error {
+		Error::Spec(err)
+	}
+}
+
 impl From<ExecutionError> for Error {
 	fn from(err: ExecutionError) -> Error {
 		Error::Execution(err)
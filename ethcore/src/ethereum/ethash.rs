@@ -246,7 +246,7 @@ impl Engine for Ethash {
 		if header.number() >= self.ethash_params.frontier_compatibility_mode_limit {
 			try!(t.check_low_s());
 		}
-		Ok(())
+		t.check_chain_id(self.signing_chain_id(header.number()))
 	}
 
 	fn verify_transaction(&self, t: &SignedTransaction, _header: &Header) -> Result<(), Error> {
@@ -51,6 +51,9 @@ pub fn new_homestead_test() -> Spec { Spec::load(include_bytes!("../../res/ether
 /// Create a new Frontier/Homestead/DAO chain spec with transition points at #5 and #8.
 pub fn new_daohardfork_test() -> Spec { Spec::load(include_bytes!("../../res/ethereum/daohardfork_test.json")) }
 
+/// Create a new Frontier chain spec with minimal difficulty, so that blocks are easily mined on the CPU.
+pub fn new_minimal_difficulty_test() -> Spec { Spec::load(include_bytes!("../../res/ethereum/minimal_difficulty_test.json")) }
+
 /// Create a new Frontier main net chain spec without genesis accounts.
 pub fn new_mainnet_like() -> Spec { Spec::load(include_bytes!("../../res/ethereum/frontier_like_test.json")) }
 
@@ -57,6 +57,9 @@ pub enum Error {
 		/// What was the stack limit
 		limit: usize
 	},
+	/// Returned when a builtin contract fails to execute, e.g. due to malformed input.
+	/// Like `OutOfGas`, all gas given to the call is consumed.
+	BuiltinFailed(BuiltinError),
 	/// Returned on evm internal error. Should never be ignored during development.
 	/// Likely to cause consensus issues.
 	#[allow(dead_code)] // created only by jit
@@ -35,3 +35,9 @@ pub use self::evm::{Evm, Error, Finalize, GasLeft, Result, CostType};
 pub use self::ext::{Ext, ContractCreateResult, MessageCallResult};
 pub use self::factory::{Factory, VMType};
 pub use self::schedule::Schedule;
+
+/// Returns the mnemonic of given instruction (empty if undefined) and the number of stack items it takes.
+pub fn instruction_info(instruction: u8) -> (&'static str, usize) {
+	let info = &instructions::INSTRUCTIONS[instruction as usize];
+	(info.name, info.args)
+}
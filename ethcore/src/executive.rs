@@ -18,10 +18,12 @@
 use common::*;
 use state::*;
 use engine::*;
+use snark_registry::REGISTRY_NAME;
 use evm::{self, Ext, Factory, Finalize};
 use externalities::*;
 use substate::*;
 use trace::{Trace, Tracer, NoopTracer, ExecutiveTracer, VMTrace, VMTracer, ExecutiveVMTracer, NoopVMTracer};
+use trace::trace::BuiltinCallResult;
 use crossbeam;
 pub use types::executed::{Executed, ExecutionResult};
 
@@ -109,12 +111,27 @@ impl<'a> Executive<'a> {
 
 	/// Execute transaction/call with tracing enabled
 	pub fn transact_with_tracer<T, V>(
+		&'a mut self,
+		t: &SignedTransaction,
+		check_nonce: bool,
+		tracer: T,
+		vm_tracer: V
+	) -> Result<Executed, ExecutionError> where T: Tracer, V: VMTracer<Output = VMTrace> {
+		self.transact_with_vm_tracer_output(t, check_nonce, tracer, vm_tracer).map(|(mut executed, vm_trace)| {
+			executed.vm_trace = vm_trace;
+			executed
+		})
+	}
+
+	/// Execute transaction/call with tracing enabled.
+	/// The output of `vm_tracer` is returned alongside the result instead of being stored in it.
+	pub fn transact_with_vm_tracer_output<T, V>(
 		&'a mut self,
 		t: &SignedTransaction,
 		check_nonce: bool,
 		mut tracer: T,
 		mut vm_tracer: V
-	) -> Result<Executed, ExecutionError> where T: Tracer, V: VMTracer {
+	) -> Result<(Executed, Option<V::Output>), ExecutionError> where T: Tracer, V: VMTracer {
 		let sender = try!(t.sender().map_err(|e| {
 			let message = format!("Transaction malformed: {:?}", e);
 			ExecutionError::TransactionMalformed(message)
@@ -195,7 +212,8 @@ impl<'a> Executive<'a> {
 		};
 
 		// finalize here!
-		Ok(try!(self.finalize(t, substate, gas_left, output, tracer.traces().pop(), vm_tracer.drain())))
+		let vm_output = vm_tracer.drain();
+		Ok((try!(self.finalize(t, substate, gas_left, output, tracer.traces().pop(), None)), vm_output))
 	}
 
 	fn exec_vm<T, V>(
@@ -250,46 +268,75 @@ impl<'a> Executive<'a> {
 
 		let delegate_call = params.code_address != params.address;
 
-		if self.engine.is_builtin(&params.code_address) {
+		let engine = self.engine;
+		let zksnark_registry = engine.zksnark_registry()
+			.and_then(|r| if r.address == params.code_address && r.is_active(self.info.number) { Some(r) } else { None });
+
+		if zksnark_registry.is_some() || engine.is_builtin(&params.code_address, self.info) {
 			// if destination is builtin, try to execute it
 
 			let default = [];
 			let data = if let Some(ref d) = params.data { d as &[u8] } else { &default as &[u8] };
 
 			let trace_info = tracer.prepare_trace_call(&params);
+			let tracing = trace_info.is_some();
+			// describes the builtin call for the trace; decoding the input is only done when tracing
+			let trace_result = |gas_used: U256, output: &[u8], error: Option<String>| if tracing {
+				let (name, arg_sizes) = match zksnark_registry {
+					Some(registry) => (REGISTRY_NAME.to_owned(), registry.arg_sizes(data)),
+					None => {
+						let builtin = engine.builtins().get(&params.code_address).expect("engine.is_builtin(&params.code_address): qed");
+						(builtin.name.clone(), builtin.arg_sizes(data))
+					},
+				};
+				Some(BuiltinCallResult {
+					name: name,
+					gas_used: gas_used,
+					arg_sizes: arg_sizes,
+					output: output.to_vec(),
+					error: error,
+				})
+			} else {
+				None
+			};
+
+			let cost = match zksnark_registry {
+				// the full cost depends on the storage, which is only read once the base cost is paid for
+				Some(registry) => match registry.base_cost(engine, self.info) {
+					base if base > params.gas => base,
+					_ => registry.cost(self.state, engine, self.info, &params.address, data),
+				},
+				None => engine.cost_of_builtin(&params.code_address, self.info, data),
+			};
+			if cost > params.gas {
+				// just drain the whole gas
+				self.state.revert_snapshot();
+
+				tracer.trace_builtin_call(trace_info, trace_result(params.gas, &[], Some("out of gas".into())), self.depth, delegate_call);
 
-			let cost = self.engine.cost_of_builtin(&params.code_address, data);
-			match cost <= params.gas {
-				true => {
-					self.engine.execute_builtin(&params.code_address, data, &mut output);
+				return Err(evm::Error::OutOfGas);
+			}
+
+			let result = match zksnark_registry {
+				Some(registry) => registry.execute(self.state, engine, self.info, &params.address, data, &mut output),
+				None => engine.execute_builtin(&params.code_address, self.info, data, &mut output),
+			};
+
+			match result {
+				Ok(()) => {
 					self.state.clear_snapshot();
 
-					// trace only top level calls to builtins to avoid DDoS attacks
-					if self.depth == 0 {
-						let mut trace_output = tracer.prepare_trace_output();
-						if let Some(mut out) = trace_output.as_mut() {
-							*out = output.to_owned();
-						}
-
-						tracer.trace_call(
-							trace_info,
-							cost,
-							trace_output,
-							self.depth,
-							vec![],
-							delegate_call
-						);
-					}
+					tracer.trace_builtin_call(trace_info, trace_result(cost, &output, None), self.depth, delegate_call);
 
 					Ok(params.gas - cost)
 				},
-				// just drain the whole gas
-				false => {
+				// builtin failed, so the call fails and drains the whole gas
+				Err(err) => {
 					self.state.revert_snapshot();
 
-					tracer.trace_failed_call(trace_info, self.depth, vec![], delegate_call);
+					tracer.trace_builtin_call(trace_info, trace_result(params.gas, &[], Some(err.to_string())), self.depth, delegate_call);
 
-					Err(evm::Error::OutOfGas)
+					Err(evm::Error::BuiltinFailed(err))
 				}
 			}
 		} else {
@@ -476,7 +523,8 @@ impl<'a> Executive<'a> {
 				| Err(evm::Error::BadJumpDestination {..})
 				| Err(evm::Error::BadInstruction {.. })
 				| Err(evm::Error::StackUnderflow {..})
-				| Err(evm::Error::OutOfStack {..}) => {
+				| Err(evm::Error::OutOfStack {..})
+				| Err(evm::Error::BuiltinFailed(_)) => {
 					self.state.revert_snapshot();
 			},
 			Ok(_) | Err(evm::Error::Internal) => {
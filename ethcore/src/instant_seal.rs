@@ -0,0 +1,101 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A development engine which seals every block as soon as it has transactions.
+
+use std::collections::BTreeMap;
+use util::hash::Address;
+use util::Bytes;
+use builtin::Builtin;
+use engine::Engine;
+use spec::CommonParams;
+use evm::Schedule;
+use env_info::EnvInfo;
+use block::ExecutedBlock;
+use account_provider::AccountProvider;
+
+/// An engine which seals blocks instantly with an empty seal; no proof-of-work and no authority key is required.
+pub struct InstantSeal {
+	params: CommonParams,
+	builtins: BTreeMap<Address, Builtin>,
+}
+
+impl InstantSeal {
+	/// Returns new instance of InstantSeal with default VM Factory
+	pub fn new(params: CommonParams, builtins: BTreeMap<Address, Builtin>) -> Self {
+		InstantSeal {
+			params: params,
+			builtins: builtins,
+		}
+	}
+}
+
+impl Engine for InstantSeal {
+	fn name(&self) -> &str {
+		"InstantSeal"
+	}
+
+	fn params(&self) -> &CommonParams {
+		&self.params
+	}
+
+	fn builtins(&self) -> &BTreeMap<Address, Builtin> {
+		&self.builtins
+	}
+
+	fn schedule(&self, _env_info: &EnvInfo) -> Schedule {
+		Schedule::new_homestead()
+	}
+
+	fn generate_seal(&self, _block: &ExecutedBlock, _accounts: Option<&AccountProvider>) -> Option<Vec<Bytes>> {
+		Some(Vec::new())
+	}
+
+	fn seals_instantly(&self) -> bool { true }
+}
+
+#[cfg(test)]
+mod tests {
+	use common::*;
+	use block::*;
+	use tests::helpers::*;
+	use spec::Spec;
+
+	#[test]
+	fn instant_can_seal() {
+		let spec = Spec::new_instant();
+		let engine = &spec.engine;
+		let genesis_header = spec.genesis_header();
+		let mut db_result = get_temp_journal_db();
+		let mut db = db_result.take();
+		spec.ensure_db_good(db.as_hashdb_mut());
+		let last_hashes = vec![genesis_header.hash()];
+		let vm_factory = Default::default();
+		let b = OpenBlock::new(engine.deref(), &vm_factory, Default::default(), false, db, &genesis_header, last_hashes, Address::default(), (3141562.into(), 31415620.into()), vec![]).unwrap();
+		let b = b.close_and_lock();
+		let seal = engine.generate_seal(b.block(), None).unwrap();
+		assert!(b.try_seal(engine.deref(), seal).is_ok());
+	}
+
+	#[test]
+	fn instant_accepts_unsealed_header() {
+		let engine = Spec::new_instant().engine;
+		let header = Header::default();
+
+		assert!(engine.verify_block_basic(&header, None).is_ok());
+		assert!(engine.verify_block_unordered(&header, None).is_ok());
+	}
+}
@@ -89,6 +89,7 @@ extern crate env_logger;
 extern crate num_cpus;
 extern crate crossbeam;
 extern crate ethjson;
+extern crate serde_json;
 extern crate bloomchain;
 extern crate ethabi;
 #[macro_use] extern crate ethcore_ipc as ipc;
@@ -96,6 +97,8 @@ extern crate rayon;
 extern crate hyper;
 extern crate ethash;
 extern crate hackishlibsnarkbindings;
+extern crate bn;
+extern crate num;
 pub extern crate ethstore;
 extern crate semver;
 extern crate ethcore_ipc_nano as nanoipc;
@@ -105,6 +108,7 @@ extern crate ethcore_devtools as devtools;
 
 pub mod account_provider;
 pub mod basic_authority;
+pub mod instant_seal;
 pub mod block;
 pub mod block_queue;
 pub mod client;
@@ -121,6 +125,8 @@ pub mod migrations;
 pub mod miner;
 pub mod snapshot;
 pub mod action_params;
+pub mod builtin;
+pub mod snark_registry;
 #[macro_use] pub mod evm;
 
 mod blooms;
@@ -133,7 +139,6 @@ mod state;
 mod account;
 mod account_db;
 mod null_engine;
-mod builtin;
 mod substate;
 mod executive;
 mod externalities;
@@ -0,0 +1,167 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Internal CPU miner searching Ethash nonces for the sealing work of `Miner`.
+
+use std::cmp::max;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use util::{H64, H256, U256, FixedHash, Hashable, encode};
+use ethash::EthashManager;
+use ethereum::ethash::Ethash;
+use client::MiningBlockChainClient;
+use miner::{MinerService, ExternalMinerService};
+
+/// Number of nonces tried before checking whether the sealing work has changed.
+const NONCES_PER_ROUND: u64 = 64;
+/// Time (in milliseconds) to wait before asking for sealing work again when there is none.
+const IDLE_PERIOD_MS: u64 = 500;
+
+/// Pool of threads mining the sealing work of a `MinerService` with the Ethash light or full (DAG) computation.
+/// Intended for small private chains; each thread reports its hashrate to the given `ExternalMinerService`.
+pub struct CpuMiner {
+	stopping: Arc<AtomicBool>,
+	threads: Vec<JoinHandle<()>>,
+}
+
+impl CpuMiner {
+	/// Starts `thread_count` mining threads. Found seals are submitted to `miner`.
+	/// With `use_dag` the threads share the full dataset of the current epoch, which is much faster
+	/// than the light computation but needs over 1GB of memory and a while to generate for every epoch.
+	pub fn start<C, M, EM>(client: &Arc<C>, miner: &Arc<M>, external_miner: Arc<EM>, thread_count: usize, use_dag: bool) -> CpuMiner where
+		C: MiningBlockChainClient + 'static,
+		M: MinerService + 'static,
+		EM: ExternalMinerService + 'static {
+
+		let stopping = Arc::new(AtomicBool::new(false));
+		let ethash = Arc::new(EthashManager::new());
+		let threads = (0..thread_count).map(|i| {
+			let client = Arc::downgrade(client);
+			let miner = Arc::downgrade(miner);
+			let external_miner = external_miner.clone();
+			let ethash = ethash.clone();
+			let stopping = stopping.clone();
+			thread::Builder::new()
+				.name(format!("Miner #{}", i))
+				.spawn(move || {
+					let id = format!("CPU miner #{}", i).sha3();
+					CpuMiner::mine(client, miner, &*external_miner, id, &ethash, use_dag, &stopping)
+				})
+				.expect("Error starting mining thread")
+		}).collect();
+
+		CpuMiner {
+			stopping: stopping,
+			threads: threads,
+		}
+	}
+
+	fn mine<C, M, EM>(client: Weak<C>, miner: Weak<M>, external_miner: &EM, id: H256, ethash: &EthashManager, use_dag: bool, stopping: &AtomicBool) where
+		C: MiningBlockChainClient,
+		M: MinerService,
+		EM: ExternalMinerService {
+
+		let mut nonce = H64::random().low_u64();
+		while !stopping.load(AtomicOrdering::Relaxed) {
+			let (client, miner) = match (client.upgrade(), miner.upgrade()) {
+				(Some(client), Some(miner)) => (client, miner),
+				_ => break,
+			};
+			let work = miner.map_sealing_work(&*client, |b| (b.hash(), b.block().header().number(), *b.block().header().difficulty()));
+			let (pow_hash, number, difficulty) = match work {
+				Some(work) => work,
+				None => {
+					thread::sleep(Duration::from_millis(IDLE_PERIOD_MS));
+					continue;
+				}
+			};
+
+			let started = Instant::now();
+			let mut hashes = 0u64;
+			while hashes < NONCES_PER_ROUND {
+				let pow = if use_dag {
+					ethash.compute_full(number, &pow_hash.0, nonce)
+				} else {
+					ethash.compute_light(number, &pow_hash.0, nonce)
+				};
+				let tried = nonce;
+				nonce = nonce.wrapping_add(1);
+				hashes += 1;
+				if Ethash::boundary_to_difficulty(&H256(pow.value)) >= difficulty {
+					let seal = vec![encode(&H256(pow.mix_hash)).to_vec(), encode(&H64::from(tried)).to_vec()];
+					trace!(target: "miner", "CPU miner found seal for #{} ({})", number, pow_hash);
+					if let Err(e) = miner.submit_seal(&*client, pow_hash, seal) {
+						debug!(target: "miner", "CPU miner seal rejected: {:?}", e);
+					}
+					break;
+				}
+			}
+
+			let elapsed = started.elapsed();
+			let millis = elapsed.as_secs() * 1000 + elapsed.subsec_nanos() as u64 / 1_000_000;
+			external_miner.submit_hashrate(U256::from(hashes * 1000 / max(millis, 1)), id);
+		}
+	}
+}
+
+impl Drop for CpuMiner {
+	fn drop(&mut self) {
+		trace!(target: "shutdown", "[CpuMiner] Closing...");
+		self.stopping.store(true, AtomicOrdering::Relaxed);
+		for t in self.threads.drain(..) {
+			if let Err(e) = t.join() {
+				warn!(target: "shutdown", "[CpuMiner] Mining thread panicked: {:?}", e);
+			}
+		}
+		trace!(target: "shutdown", "[CpuMiner] Closed.");
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::thread;
+	use std::time::{Duration, Instant};
+	use devtools::RandomTempPath;
+	use util::IoChannel;
+	use client::{Client, ClientConfig, BlockChainClient};
+	use ethereum;
+	use miner::{Miner, ExternalMiner};
+	use super::CpuMiner;
+
+	#[test]
+	// generates the Ethash light cache in ~/.ethash and may take a while; run explicitly with `--ignored`
+	#[ignore]
+	fn should_mine_block_with_minimal_difficulty() {
+		// given
+		let temp = RandomTempPath::new();
+		let miner = Arc::new(Miner::with_spec(ethereum::new_minimal_difficulty_test()));
+		let client = Client::new(ClientConfig::default(), ethereum::new_minimal_difficulty_test(), temp.as_path(), miner.clone(), IoChannel::disconnected()).unwrap();
+
+		// when
+		let cpu_miner = CpuMiner::start(&client, &miner, Arc::new(ExternalMiner::default()), 1, false);
+		let started = Instant::now();
+		while client.chain_info().best_block_number == 0 && started.elapsed() < Duration::from_secs(120) {
+			thread::sleep(Duration::from_millis(100));
+		}
+		drop(cpu_miner);
+
+		// then
+		assert!(client.chain_info().best_block_number >= 1);
+	}
+}
@@ -0,0 +1,170 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Persistent store of local transactions.
+
+use std::path::Path;
+use util::*;
+use transaction::SignedTransaction;
+use miner::TransactionCondition;
+
+/// Keeps transactions submitted through this node (`TransactionOrigin::Local`) on disk,
+/// so that they survive a restart until they are mined or become invalid.
+/// Transactions held until their condition is met are kept together with the condition.
+pub struct LocalTransactionsStore {
+	db: Database,
+	/// Hashes of the stored transactions, so that pruning doesn't have to read the whole DB.
+	hashes: RwLock<HashSet<H256>>,
+}
+
+impl LocalTransactionsStore {
+	/// Opens (or creates) the store at `path`.
+	pub fn open(path: &Path) -> Result<LocalTransactionsStore, String> {
+		let path = try!(path.to_str().ok_or_else(|| format!("Invalid path: {:?}", path)));
+		let db = try!(Database::open_default(path));
+		let hashes = db.iter().map(|(key, _)| H256::from_slice(&key)).collect();
+		Ok(LocalTransactionsStore {
+			db: db,
+			hashes: RwLock::new(hashes),
+		})
+	}
+
+	/// Stores transaction `t` together with the `condition` it is held for, if any.
+	pub fn insert(&self, t: &SignedTransaction, condition: Option<&TransactionCondition>) {
+		let mut s = RlpStream::new_list(if condition.is_some() { 2 } else { 1 });
+		s.append(t);
+		if let Some(condition) = condition {
+			s.append(condition);
+		}
+		let hash = t.hash();
+		match self.db.put(&hash, &s.out()) {
+			Ok(_) => { self.hashes.write().insert(hash); },
+			Err(e) => warn!(target: "miner", "Failed to store local transaction {:?}: {}", hash, e),
+		}
+	}
+
+	/// Removes transaction with given `hash`, if stored.
+	pub fn remove(&self, hash: &H256) {
+		if !self.hashes.write().remove(hash) {
+			return;
+		}
+		if let Err(e) = self.db.delete(hash) {
+			warn!(target: "miner", "Failed to remove local transaction {:?} from the store: {}", hash, e);
+		}
+	}
+
+	/// Returns hashes of all stored transactions.
+	pub fn hashes(&self) -> Vec<H256> {
+		self.hashes.read().iter().cloned().collect()
+	}
+
+	/// Returns all stored transactions with their conditions. Entries which cannot be decoded are dropped.
+	pub fn transactions(&self) -> Vec<(SignedTransaction, Option<TransactionCondition>)> {
+		let mut transactions = Vec::new();
+		for (key, value) in self.db.iter() {
+			match decode_entry(&value) {
+				Ok(entry) => transactions.push(entry),
+				Err(e) => {
+					warn!(target: "miner", "Dropping invalid local transaction from the store: {:?}", e);
+					self.remove(&H256::from_slice(&key));
+				},
+			}
+		}
+		transactions
+	}
+}
+
+fn decode_entry(bytes: &[u8]) -> Result<(SignedTransaction, Option<TransactionCondition>), DecoderError> {
+	let rlp = UntrustedRlp::new(bytes);
+	let transaction = try!(rlp.val_at(0));
+	let condition = match rlp.item_count() {
+		1 => None,
+		2 => Some(try!(rlp.val_at(1))),
+		_ => return Err(DecoderError::RlpIncorrectListLen),
+	};
+	Ok((transaction, condition))
+}
+
+impl Encodable for TransactionCondition {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(2);
+		match *self {
+			TransactionCondition::Number(number) => s.append(&0u32).append(&number),
+			TransactionCondition::Timestamp(timestamp) => s.append(&1u32).append(&timestamp),
+		};
+	}
+}
+
+impl Decodable for TransactionCondition {
+	fn decode<D>(decoder: &D) -> Result<Self, DecoderError> where D: Decoder {
+		let d = decoder.as_rlp();
+		if d.item_count() != 2 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		match try!(d.val_at::<u32>(0)) {
+			0 => Ok(TransactionCondition::Number(try!(d.val_at(1)))),
+			1 => Ok(TransactionCondition::Timestamp(try!(d.val_at(1)))),
+			_ => Err(DecoderError::Custom("Unknown transaction condition.")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use util::*;
+	use devtools::RandomTempPath;
+	use transaction::{Transaction, Action};
+	use miner::TransactionCondition;
+	use super::LocalTransactionsStore;
+
+	#[test]
+	fn should_keep_transactions_across_reopening() {
+		let temp = RandomTempPath::new();
+		let keypair = KeyPair::create().unwrap();
+		let tx = |nonce: u64| Transaction {
+			action: Action::Create,
+			value: U256::from(100),
+			data: vec![],
+			gas: U256::from(100_000),
+			gas_price: U256::one(),
+			nonce: U256::from(nonce)
+		}.sign(keypair.secret());
+		let (t1, t2) = (tx(0), tx(1));
+		let condition = TransactionCondition::Timestamp(1_500_000_000);
+
+		{
+			let store = LocalTransactionsStore::open(temp.as_path()).unwrap();
+			store.insert(&t1, None);
+			store.insert(&t2, Some(&condition));
+		}
+		{
+			let store = LocalTransactionsStore::open(temp.as_path()).unwrap();
+			let mut hashes = store.hashes();
+			hashes.sort();
+			let mut expected = vec![t1.hash(), t2.hash()];
+			expected.sort();
+			assert_eq!(hashes, expected);
+			let mut transactions = store.transactions();
+			transactions.sort_by_key(|&(ref t, _)| t.nonce);
+			assert_eq!(transactions, vec![(t1.clone(), None), (t2.clone(), Some(condition))]);
+			store.remove(&t1.hash());
+			store.remove(&t2.hash());
+		}
+		let store = LocalTransactionsStore::open(temp.as_path()).unwrap();
+		assert!(store.hashes().is_empty());
+		assert!(store.transactions().is_empty());
+	}
+}
@@ -22,16 +22,19 @@ use util::*;
 use util::using_queue::{UsingQueue, GetAction};
 use account_provider::AccountProvider;
 use views::{BlockView, HeaderView};
+use header::BlockNumber;
 use state::State;
-use client::{MiningBlockChainClient, Executive, Executed, EnvInfo, TransactOptions, BlockID, CallAnalytics};
+use client::{MiningBlockChainClient, Executive, Executed, EnvInfo, TransactOptions, BlockID, CallAnalytics, StateOverride};
 use block::{ClosedBlock, IsBlock, Block};
 use error::*;
 use transaction::SignedTransaction;
 use receipt::Receipt;
 use spec::Spec;
 use engine::Engine;
-use miner::{MinerService, MinerStatus, TransactionQueue, AccountDetails, TransactionOrigin};
+use miner::{MinerService, MinerStatus, TransactionQueue, AccountDetails, TransactionOrigin, LocalTransactionsStore, TransactionCondition,
+	QueuedTransaction, FutureReason};
 use miner::work_notify::WorkPoster;
+use miner::transaction_queue::DEFAULT_REPLACE_BUMP;
 use client::TransactionImportResult;
 use miner::price_info::PriceInfo;
 
@@ -64,6 +67,12 @@ pub struct MinerOptions {
 	pub tx_gas_limit: U256,
 	/// Maximum size of the transaction queue.
 	pub tx_queue_size: usize,
+	/// Maximum number of transactions from a single sender in the transaction queue.
+	pub tx_queue_per_sender: usize,
+	/// Maximum total gas of transactions in the transaction queue.
+	pub tx_queue_gas_limit: U256,
+	/// Gas price increase (in percents) required to replace a queued transaction with the same sender and nonce.
+	pub tx_replace_bump: usize,
 	/// Whether we should fallback to providing all the queue's transactions or just pending.
 	pub pending_set: PendingSet,
 	/// How many historical work packages can we store before running out?
@@ -81,6 +90,9 @@ impl Default for MinerOptions {
 			reseal_on_own_tx: true,
 			tx_gas_limit: !U256::zero(),
 			tx_queue_size: 1024,
+			tx_queue_per_sender: 64,
+			tx_queue_gas_limit: !U256::zero(),
+			tx_replace_bump: DEFAULT_REPLACE_BUMP,
 			pending_set: PendingSet::AlwaysQueue,
 			reseal_min_period: Duration::from_secs(0),
 			work_queue_size: 20,
@@ -176,6 +188,8 @@ pub struct Miner {
 	accounts: Option<Arc<AccountProvider>>,
 	work_poster: Option<WorkPoster>,
 	gas_pricer: Mutex<GasPricer>,
+	local_store: RwLock<Option<LocalTransactionsStore>>,
+	conditional_transactions: Mutex<Vec<(SignedTransaction, TransactionCondition)>>,
 }
 
 impl Miner {
@@ -195,13 +209,19 @@ impl Miner {
 			spec: spec,
 			work_poster: None,
 			gas_pricer: Mutex::new(GasPricer::new_fixed(20_000_000_000u64.into())),
+			local_store: RwLock::new(None),
+			conditional_transactions: Mutex::new(Vec::new()),
 		}
 	}
 
 	/// Creates new instance of miner
 	pub fn new(options: MinerOptions, gas_pricer: GasPricer, spec: Spec, accounts: Option<Arc<AccountProvider>>) -> Arc<Miner> {
 		let work_poster = if !options.new_work_notify.is_empty() { Some(WorkPoster::new(&options.new_work_notify)) } else { None };
-		let txq = Arc::new(Mutex::new(TransactionQueue::with_limits(options.tx_queue_size, options.tx_gas_limit)));
+		let mut txq = TransactionQueue::with_limits(options.tx_queue_size, options.tx_gas_limit);
+		txq.set_per_sender_limit(options.tx_queue_per_sender);
+		txq.set_total_gas_limit(options.tx_queue_gas_limit);
+		txq.set_replace_bump(options.tx_replace_bump);
+		let txq = Arc::new(Mutex::new(txq));
 		Arc::new(Miner {
 			transaction_queue: txq,
 			sealing_enabled: AtomicBool::new(options.force_sealing || !options.new_work_notify.is_empty()),
@@ -216,9 +236,86 @@ impl Miner {
 			spec: spec,
 			work_poster: work_poster,
 			gas_pricer: Mutex::new(gas_pricer),
+			local_store: RwLock::new(None),
+			conditional_transactions: Mutex::new(Vec::new()),
 		})
 	}
 
+	/// Persist local transactions in `store` from now on. Transactions stored there by a previous run
+	/// are imported into the queue (or held until their condition is met) again;
+	/// the ones which are no longer valid are dropped from the store.
+	pub fn set_local_transactions_store(&self, chain: &MiningBlockChainClient, store: LocalTransactionsStore) {
+		let mut transactions = Vec::new();
+		{
+			let mut conditional = self.conditional_transactions.lock();
+			for (t, condition) in store.transactions() {
+				match condition {
+					Some(condition) => conditional.push((t, condition)),
+					None => transactions.push(t),
+				}
+			}
+		}
+		*self.local_store.write() = Some(store);
+		self.import_local_transactions(chain, transactions);
+		self.promote_conditional_transactions(chain);
+	}
+
+	/// Imports local `transactions` to the queue. Imported ones are kept in the local transactions store
+	/// (without any condition), the ones which are not valid are dropped from it.
+	fn import_local_transactions(&self, chain: &MiningBlockChainClient, transactions: Vec<SignedTransaction>) {
+		let results = {
+			let mut transaction_queue = self.transaction_queue.lock();
+			self.add_transactions_to_queue(chain, transactions.clone(), TransactionOrigin::Local, &mut transaction_queue)
+		};
+		let store = self.local_store.read();
+		for (t, result) in transactions.iter().zip(results) {
+			match result {
+				Ok(_) | Err(Error::Transaction(TransactionError::AlreadyImported)) => {
+					if let Some(ref store) = *store {
+						store.insert(t, None);
+					}
+				},
+				Err(e) => {
+					debug!(target: "own_tx", "Dropping local transaction {:?}: {:?}", t.hash(), e);
+					if let Some(ref store) = *store {
+						store.remove(&t.hash());
+					}
+				},
+			}
+		}
+	}
+
+	/// Imports held transactions whose condition is met by the best block to the queue.
+	fn promote_conditional_transactions(&self, chain: &MiningBlockChainClient) {
+		let (number, timestamp) = best_block_number_and_timestamp(chain);
+		let due: Vec<_> = {
+			let mut conditional = self.conditional_transactions.lock();
+			let (due, held): (Vec<_>, Vec<_>) = conditional.drain(..)
+				.partition(|&(_, ref condition)| condition.is_met(number, timestamp));
+			*conditional = held;
+			due.into_iter().map(|(t, _)| t).collect()
+		};
+		if !due.is_empty() {
+			trace!(target: "own_tx", "Importing {} transactions with met conditions", due.len());
+			self.import_local_transactions(chain, due);
+		}
+	}
+
+	/// Drop transactions which are neither in the queue (mined or invalid) nor held until their condition
+	/// is met from the local transactions store.
+	fn prune_local_transactions(&self) {
+		let transaction_queue = self.transaction_queue.lock();
+		let conditional = self.conditional_transactions.lock();
+		if let Some(ref store) = *self.local_store.read() {
+			for hash in store.hashes() {
+				let is_held = conditional.iter().any(|&(ref held, _)| held.hash() == hash);
+				if !is_held && transaction_queue.find(&hash).is_none() {
+					store.remove(&hash);
+				}
+			}
+		}
+	}
+
 	fn engine(&self) -> &Engine {
 		self.spec.engine.deref()
 	}
@@ -420,8 +517,10 @@ impl Miner {
 			balance: chain.latest_balance(a),
 		};
 
+		let chain_id = self.engine().signing_chain_id(chain.chain_info().best_block_number + 1);
+
 		transactions.into_iter()
-			.map(|tx| transaction_queue.add(tx, &fetch_account, origin))
+			.map(|tx| tx.check_chain_id(chain_id).and_then(|_| transaction_queue.add(tx, &fetch_account, origin)))
 			.collect()
 	}
 
@@ -431,6 +530,12 @@ impl Miner {
 
 const SEALING_TIMEOUT_IN_BLOCKS : u64 = 5;
 
+/// Number and timestamp of the best block, against which transaction conditions are checked.
+fn best_block_number_and_timestamp(chain: &MiningBlockChainClient) -> (BlockNumber, u64) {
+	let timestamp = chain.block_header(BlockID::Latest).map_or(0, |h| HeaderView::new(&h).timestamp());
+	(chain.chain_info().best_block_number, timestamp)
+}
+
 impl MinerService for Miner {
 
 	fn clear_and_reset(&self, chain: &MiningBlockChainClient) {
@@ -452,7 +557,7 @@ impl MinerService for Miner {
 		}
 	}
 
-	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, ExecutionError> {
+	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, overrides: &StateOverride, analytics: CallAnalytics) -> Result<Executed, CallError> {
 		let sealing_work = self.sealing_work.lock();
 		match sealing_work.peek_last_ref() {
 			Some(work) => {
@@ -472,6 +577,9 @@ impl MinerService for Miner {
 				};
 				// that's just a copy of the state.
 				let mut state = block.state().clone();
+				state.apply_overrides(overrides);
+				let original_state = if analytics.state_diffing { Some(state.clone()) } else { None };
+
 				let sender = try!(t.sender().map_err(|e| {
 					let message = format!("Transaction malformed: {:?}", e);
 					ExecutionError::TransactionMalformed(message)
@@ -486,15 +594,15 @@ impl MinerService for Miner {
 				let mut ret = Executive::new(&mut state, &env_info, self.engine(), chain.vm_factory()).transact(t, options);
 
 				// TODO gav move this into Executive.
-				if analytics.state_diffing {
+				if let Some(original) = original_state {
 					if let Ok(ref mut x) = ret {
-						x.state_diff = Some(state.diff_from(block.state().clone()));
+						x.state_diff = Some(state.diff_from(original));
 					}
 				}
-				ret
+				ret.map_err(Into::into)
 			},
 			None => {
-				chain.call(t, analytics)
+				chain.call(t, BlockID::Latest, overrides, analytics)
 			}
 		}
 	}
@@ -634,6 +742,11 @@ impl MinerService for Miner {
 				Ok(ref res) => {
 					trace!(target: "own_tx", "Imported transaction to {:?} (hash: {:?})", res, hash);
 					trace!(target: "own_tx", "Status: {:?}", transaction_queue.status());
+					if let Some(ref store) = *self.local_store.read() {
+						if let Some(t) = transaction_queue.find(&hash) {
+							store.insert(&t, None);
+						}
+					}
 				},
 				Err(ref e) => {
 					trace!(target: "own_tx", "Failed to import transaction {:?} (hash: {:?})", e, hash);
@@ -648,7 +761,9 @@ impl MinerService for Miner {
 		// | NOTE Code below requires transaction_queue and sealing_work locks.     |
 		// | Make sure to release the locks before calling that method.             |
 		// --------------------------------------------------------------------------
-		if imported.is_ok() && self.options.reseal_on_own_tx && self.tx_reseal_allowed() {
+		// Engines which seal instantly get a new block for every own transaction.
+		let seal_now = self.engine().seals_instantly() || (self.options.reseal_on_own_tx && self.tx_reseal_allowed());
+		if imported.is_ok() && seal_now {
 			// Make sure to do it after transaction is imported and lock is droped.
 			// We need to create pending block and enable sealing
 			let prepared = self.enable_and_prepare_sealing(chain);
@@ -662,11 +777,59 @@ impl MinerService for Miner {
 		imported
 	}
 
+	fn import_conditional_transaction(
+		&self,
+		chain: &MiningBlockChainClient,
+		transaction: SignedTransaction,
+		condition: TransactionCondition,
+	) -> Result<(), Error> {
+		let (number, timestamp) = best_block_number_and_timestamp(chain);
+		if condition.is_met(number, timestamp) {
+			return self.import_own_transaction(chain, transaction).map(|_| ());
+		}
+
+		try!(transaction.sender());
+		let hash = transaction.hash();
+		{
+			let mut conditional = self.conditional_transactions.lock();
+			if conditional.iter().any(|&(ref t, _)| t.hash() == hash) {
+				return Err(Error::Transaction(TransactionError::AlreadyImported));
+			}
+			trace!(target: "own_tx", "Holding transaction {:?} until {:?}", hash, condition);
+			conditional.push((transaction.clone(), condition));
+		}
+		if let Some(ref store) = *self.local_store.read() {
+			store.insert(&transaction, Some(&condition));
+		}
+		Ok(())
+	}
+
 	fn all_transactions(&self) -> Vec<SignedTransaction> {
 		let queue = self.transaction_queue.lock();
 		queue.top_transactions()
 	}
 
+	fn queued_transactions(&self, chain: &MiningBlockChainClient) -> Vec<QueuedTransaction> {
+		let mut transactions = self.transaction_queue.lock().queued_transactions();
+		// The queue only knows about nonces; a future transaction might also wait for funds.
+		for queued in transactions.iter_mut().filter(|queued| queued.future.is_some()) {
+			let t = &queued.transaction;
+			// A missing transaction with lower nonce blocks this one regardless of funds.
+			if let Some(FutureReason::NonceGap { expected }) = queued.future {
+				if expected != t.nonce {
+					continue;
+				}
+			}
+			let sender = t.sender().expect("Transactions in the queue have verified senders.");
+			let balance = chain.latest_balance(&sender);
+			let cost = t.value + t.gas_price * t.gas;
+			if balance < cost {
+				queued.future = Some(FutureReason::InsufficientBalance { balance: balance, cost: cost });
+			}
+		}
+		transactions
+	}
+
 	fn pending_transactions(&self) -> Vec<SignedTransaction> {
 		let queue = self.transaction_queue.lock();
 		let sw = self.sealing_work.lock();
@@ -723,7 +886,13 @@ impl MinerService for Miner {
 	}
 
 	fn last_nonce(&self, address: &Address) -> Option<U256> {
-		self.transaction_queue.lock().last_nonce(address)
+		let queued = self.transaction_queue.lock().last_nonce(address);
+		let held = self.conditional_transactions.lock().iter()
+			.filter(|&&(ref t, _)| t.sender().ok().as_ref() == Some(address))
+			.map(|&(ref t, _)| t.nonce)
+			.max();
+		// `None` is less than any nonce
+		cmp::max(queued, held)
 	}
 
 	fn update_sealing(&self, chain: &MiningBlockChainClient) {
@@ -836,6 +1005,12 @@ impl MinerService for Miner {
 			});
 		}
 
+		// Release held transactions which became due...
+		self.promote_conditional_transactions(chain);
+
+		// ...and forget local transactions which got mined or dropped, they don't need to survive a restart anymore.
+		self.prune_local_transactions();
+
 		// --------------------------------------------------------------------------
 		// | NOTE Code below requires transaction_queue and sealing_work locks.     |
 		// | Make sure to release the locks before calling that method.             |
@@ -856,6 +1031,8 @@ mod tests {
 	use types::transaction::{Transaction, Action};
 	use block::*;
 	use spec::Spec;
+	use devtools::RandomTempPath;
+	use miner::{LocalTransactionsStore, TransactionCondition, FutureReason};
 
 	#[test]
 	fn should_prepare_block_to_seal() {
@@ -899,6 +1076,9 @@ mod tests {
 				reseal_min_period: Duration::from_secs(5),
 				tx_gas_limit: !U256::zero(),
 				tx_queue_size: 1024,
+				tx_queue_per_sender: 64,
+				tx_queue_gas_limit: !U256::zero(),
+				tx_replace_bump: 10,
 				pending_set: PendingSet::AlwaysSealing,
 				work_queue_size: 5,
 				enable_resubmission: true,
@@ -968,4 +1148,122 @@ mod tests {
 		// This method will let us know if pending block was created (before calling that method)
 		assert_eq!(miner.enable_and_prepare_sealing(&client), true);
 	}
+
+	#[test]
+	fn should_restore_local_transactions_from_store() {
+		// given
+		let temp = RandomTempPath::new();
+		let client = TestBlockChainClient::default();
+		let transaction = {
+			let keypair = KeyPair::create().unwrap();
+			Transaction {
+				action: Action::Create,
+				value: U256::zero(),
+				data: "3331600055".from_hex().unwrap(),
+				gas: U256::from(100_000),
+				gas_price: U256::zero(),
+				nonce: U256::zero(),
+			}.sign(keypair.secret())
+		};
+		{
+			let miner = miner();
+			miner.set_local_transactions_store(&client, LocalTransactionsStore::open(temp.as_path()).unwrap());
+			miner.import_own_transaction(&client, transaction.clone()).unwrap();
+		}
+
+		// when
+		let miner = miner();
+		miner.set_local_transactions_store(&client, LocalTransactionsStore::open(temp.as_path()).unwrap());
+
+		// then
+		assert_eq!(miner.all_transactions(), vec![transaction]);
+	}
+
+	#[test]
+	fn should_hold_conditional_transaction_until_block_number_across_restarts() {
+		// given
+		let temp = RandomTempPath::new();
+		let client = TestBlockChainClient::default();
+		let transaction = {
+			let keypair = KeyPair::create().unwrap();
+			Transaction {
+				action: Action::Create,
+				value: U256::zero(),
+				data: "3331600055".from_hex().unwrap(),
+				gas: U256::from(100_000),
+				gas_price: U256::zero(),
+				nonce: U256::zero(),
+			}.sign(keypair.secret())
+		};
+		{
+			let miner = miner();
+			miner.set_local_transactions_store(&client, LocalTransactionsStore::open(temp.as_path()).unwrap());
+			miner.import_conditional_transaction(&client, transaction.clone(), TransactionCondition::Number(2)).unwrap();
+			assert!(miner.all_transactions().is_empty());
+		}
+		let miner = miner();
+		miner.set_local_transactions_store(&client, LocalTransactionsStore::open(temp.as_path()).unwrap());
+		assert!(miner.all_transactions().is_empty());
+
+		// when
+		client.add_blocks(2, EachBlockWith::Nothing);
+		miner.chain_new_blocks(&client, &[], &[], &[], &[]);
+
+		// then
+		assert_eq!(miner.all_transactions(), vec![transaction]);
+	}
+
+	#[test]
+	fn should_reserve_nonces_of_held_transactions() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let keypair = KeyPair::create().unwrap();
+		let transaction = |nonce: u64| Transaction {
+			action: Action::Create,
+			value: U256::zero(),
+			data: "3331600055".from_hex().unwrap(),
+			gas: U256::from(100_000),
+			gas_price: U256::zero(),
+			nonce: U256::from(nonce),
+		}.sign(keypair.secret());
+		miner.import_conditional_transaction(&client, transaction(0), TransactionCondition::Number(2)).unwrap();
+
+		// when
+		let nonce = miner.last_nonce(&keypair.address()).map_or(0, |n| n.low_u64() + 1);
+		miner.import_own_transaction(&client, transaction(nonce)).unwrap();
+		client.add_blocks(2, EachBlockWith::Nothing);
+		miner.chain_new_blocks(&client, &[], &[], &[], &[]);
+
+		// then
+		assert_eq!(nonce, 1);
+		assert_eq!(miner.all_transactions(), vec![transaction(0), transaction(1)]);
+	}
+
+	#[test]
+	fn should_keep_nonce_gap_as_reason_of_future_transaction_without_funds() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let keypair = KeyPair::create().unwrap();
+		let transaction = Transaction {
+			action: Action::Create,
+			value: U256::zero(),
+			data: "3331600055".from_hex().unwrap(),
+			gas: U256::from(100_000),
+			gas_price: U256::one(),
+			nonce: U256::one(),
+		}.sign(keypair.secret());
+		client.set_balance(keypair.address(), U256::from(100_000));
+		let res = miner.import_external_transactions(&client, vec![transaction]).pop().unwrap();
+		assert_eq!(res.unwrap(), TransactionImportResult::Future);
+
+		// when
+		client.set_balance(keypair.address(), U256::zero());
+		let queued = miner.queued_transactions(&client);
+
+		// then
+		assert_eq!(queued.len(), 1);
+		assert_eq!(queued[0].future, Some(FutureReason::NonceGap { expected: U256::zero() }));
+	}
 }
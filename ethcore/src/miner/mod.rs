@@ -47,19 +47,24 @@ mod external;
 mod transaction_queue;
 mod work_notify;
 mod price_info;
+mod local_transactions;
+mod cpu_miner;
 
-pub use self::transaction_queue::{TransactionQueue, AccountDetails, TransactionOrigin};
+pub use self::transaction_queue::{TransactionQueue, AccountDetails, TransactionOrigin, QueuedTransaction, FutureReason};
 pub use self::miner::{Miner, MinerOptions, PendingSet, GasPricer, GasPriceCalibratorOptions};
 pub use self::external::{ExternalMiner, ExternalMinerService};
+pub use self::local_transactions::LocalTransactionsStore;
+pub use self::cpu_miner::CpuMiner;
 pub use client::TransactionImportResult;
 
 use std::collections::BTreeMap;
 use util::{H256, U256, Address, Bytes};
-use client::{MiningBlockChainClient, Executed, CallAnalytics};
+use client::{MiningBlockChainClient, Executed, CallAnalytics, StateOverride};
 use block::ClosedBlock;
 use receipt::Receipt;
-use error::{Error, ExecutionError};
+use error::{Error, CallError};
 use transaction::SignedTransaction;
+use header::BlockNumber;
 
 /// Miner client API
 pub trait MinerService : Send + Sync {
@@ -115,6 +120,10 @@ pub trait MinerService : Send + Sync {
 	fn import_own_transaction(&self, chain: &MiningBlockChainClient, transaction: SignedTransaction) ->
 		Result<TransactionImportResult, Error>;
 
+	/// Holds own transaction until `condition` is met and imports it to the queue afterwards.
+	fn import_conditional_transaction(&self, chain: &MiningBlockChainClient, transaction: SignedTransaction, condition: TransactionCondition) ->
+		Result<(), Error>;
+
 	/// Returns hashes of transactions currently in pending
 	fn pending_transactions_hashes(&self) -> Vec<H256>;
 
@@ -141,13 +150,16 @@ pub trait MinerService : Send + Sync {
 	/// Get a list of all transactions.
 	fn all_transactions(&self) -> Vec<SignedTransaction>;
 
+	/// Get all transactions in the queue with their origin and the reason why future ones can't be mined yet.
+	fn queued_transactions(&self, chain: &MiningBlockChainClient) -> Vec<QueuedTransaction>;
+
 	/// Get a list of all pending transactions.
 	fn pending_transactions(&self) -> Vec<SignedTransaction>;
 
 	/// Get a list of all pending receipts.
 	fn pending_receipts(&self) -> BTreeMap<H256, Receipt>;
 
-	/// Returns highest transaction nonce for given address.
+	/// Returns highest transaction nonce for given address, including transactions held until their condition is met.
 	fn last_nonce(&self, address: &Address) -> Option<U256>;
 
 	/// Suggested gas price.
@@ -159,8 +171,8 @@ pub trait MinerService : Send + Sync {
 	/// Latest account balance in pending state.
 	fn balance(&self, chain: &MiningBlockChainClient, address: &Address) -> U256;
 
-	/// Call into contract code using pending state.
-	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, analytics: CallAnalytics) -> Result<Executed, ExecutionError>;
+	/// Call into contract code using pending state modified by `overrides`.
+	fn call(&self, chain: &MiningBlockChainClient, t: &SignedTransaction, overrides: &StateOverride, analytics: CallAnalytics) -> Result<Executed, CallError>;
 
 	/// Get storage value in pending state.
 	fn storage_at(&self, chain: &MiningBlockChainClient, address: &Address, position: &H256) -> H256;
@@ -172,6 +184,25 @@ pub trait MinerService : Send + Sync {
 	fn code(&self, chain: &MiningBlockChainClient, address: &Address) -> Option<Bytes>;
 }
 
+/// Condition which has to be met before a held transaction is imported to the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionCondition {
+	/// Met once the best block has at least given number.
+	Number(BlockNumber),
+	/// Met once the best block has at least given timestamp.
+	Timestamp(u64),
+}
+
+impl TransactionCondition {
+	/// Checks if the condition is met by the best block with given `number` and `timestamp`.
+	pub fn is_met(&self, number: BlockNumber, timestamp: u64) -> bool {
+		match *self {
+			TransactionCondition::Number(n) => number >= n,
+			TransactionCondition::Timestamp(t) => timestamp >= t,
+		}
+	}
+}
+
 /// Mining status
 #[derive(Debug)]
 pub struct MinerStatus {
@@ -131,6 +131,8 @@ struct TransactionOrder {
 	/// Gas Price of the transaction.
 	/// Low gas price = Low priority (processed later)
 	gas_price: U256,
+	/// Gas of the transaction (used to bound total gas held in the set)
+	gas: U256,
 	/// Hash to identify associated transaction
 	hash: H256,
 	/// Origin of the transaction
@@ -143,6 +145,7 @@ impl TransactionOrder {
 		TransactionOrder {
 			nonce_height: tx.nonce() - base_nonce,
 			gas_price: tx.transaction.gas_price,
+			gas: tx.transaction.gas,
 			hash: tx.hash(),
 			origin: tx.origin,
 		}
@@ -223,46 +226,94 @@ impl VerifiedTransaction {
 
 /// Holds transactions accessible by (address, nonce) and by priority
 ///
-/// `TransactionSet` keeps number of entries below limit, but it doesn't
-/// automatically happen during `insert/remove` operations.
+/// `TransactionSet` keeps number of entries (in total and per sender) and their total gas below limits,
+/// but it doesn't automatically happen during `insert/remove` operations.
 /// You have to call `enforce_limit` to remove lowest priority transactions from set.
 struct TransactionSet {
 	by_priority: BTreeSet<TransactionOrder>,
 	by_address: Table<Address, U256, TransactionOrder>,
 	limit: usize,
+	per_sender_limit: usize,
+	/// Total gas of all transactions in this set.
+	gas: U256,
+	/// Number of non-local transactions of every sender in this set.
+	external_by_sender: HashMap<Address, usize>,
+	/// Number of senders with more than `per_sender_limit` non-local transactions in this set.
+	senders_over_limit: usize,
+	evicted: usize,
 }
 
 impl TransactionSet {
 	/// Inserts `TransactionOrder` to this set
 	fn insert(&mut self, sender: Address, nonce: U256, order: TransactionOrder) -> Option<TransactionOrder> {
+		self.gas = self.gas + order.gas;
+		self.note_inserted(&sender, &order);
 		self.by_priority.insert(order.clone());
 		let r = self.by_address.insert(sender, nonce, order);
 		// If transaction was replaced remove it from priority queue
 		if let Some(ref old_order) = r {
 			self.by_priority.remove(old_order);
+			self.gas = self.gas - old_order.gas;
+			self.note_removed(&sender, old_order);
 		}
 		assert_eq!(self.by_priority.len(), self.by_address.len());
 		r
 	}
 
-	/// Remove low priority transactions if there is more then specified by given `limit`.
+	/// Remove low priority transactions if there is more then specified by limits.
+	///
+	/// First each sender is trimmed to `per_sender_limit` transactions. Then, as long as there are more than `limit`
+	/// transactions or more than `gas_limit` gas in the set, the sender with most transactions (the one with
+	/// the cheapest transaction if there are several) loses its lowest priority transaction.
+	/// Local transactions are never removed.
 	///
 	/// It drops transactions from this set but also removes associated `VerifiedTransaction`.
 	/// Returns addresses and lowest nonces of transactions removed because of limit.
-	fn enforce_limit(&mut self, by_hash: &mut HashMap<H256, VerifiedTransaction>) -> Option<HashMap<Address, U256>> {
-		let len = self.by_priority.len();
-		if len <= self.limit {
+	fn enforce_limit(&mut self, gas_limit: U256, by_hash: &mut HashMap<H256, VerifiedTransaction>) -> Option<HashMap<Address, U256>> {
+		let mut len = self.by_priority.len();
+		let mut gas = self.gas;
+		if len <= self.limit && self.senders_over_limit == 0 && gas <= gas_limit {
 			return None;
 		}
-		let to_drop : Vec<(Address, U256)> = {
-			self.by_priority
-				.iter()
-				.skip(self.limit)
-				.map(|order| by_hash.get(&order.hash)
-					.expect("All transactions in `self.by_priority` and `self.by_address` are kept in sync with `by_hash`."))
-				.map(|tx| (tx.sender(), tx.nonce()))
-				.collect()
-		};
+
+		// External transactions of every sender: (nonce, gas, gas_price), highest priority first.
+		let mut by_sender: HashMap<Address, Vec<(U256, U256, U256)>> = HashMap::new();
+		for order in self.by_priority.iter().filter(|order| order.origin != TransactionOrigin::Local) {
+			let tx = by_hash.get(&order.hash)
+				.expect("All transactions in `self.by_priority` and `self.by_address` are kept in sync with `by_hash`.");
+			by_sender.entry(tx.sender()).or_insert_with(Vec::new).push((tx.nonce(), order.gas, order.gas_price));
+		}
+
+		let mut to_drop: Vec<(Address, U256)> = Vec::new();
+		for (sender, txs) in by_sender.iter_mut() {
+			while txs.len() > self.per_sender_limit {
+				let (nonce, tx_gas, _) = txs.pop().expect("There are more transactions than the limit; qed");
+				len -= 1;
+				gas = gas - tx_gas;
+				to_drop.push((*sender, nonce));
+			}
+		}
+
+		while len > self.limit || gas > gas_limit {
+			let offender = by_sender.iter()
+				.filter_map(|(sender, txs)| txs.last().map(|&(_, _, gas_price)| (txs.len(), !gas_price, *sender)))
+				.max();
+			let sender = match offender {
+				Some((_, _, sender)) => sender,
+				// Only local transactions left
+				None => break,
+			};
+			let (nonce, tx_gas, _) = by_sender.get_mut(&sender).and_then(|txs| txs.pop())
+				.expect("Sender was just found with non-empty list of transactions; qed");
+			len -= 1;
+			gas = gas - tx_gas;
+			to_drop.push((sender, nonce));
+		}
+
+		if to_drop.is_empty() {
+			return None;
+		}
+		self.evicted += to_drop.len();
 
 		Some(to_drop.into_iter()
 			.fold(HashMap::new(), |mut removed, (sender, nonce)| {
@@ -282,6 +333,8 @@ impl TransactionSet {
 	fn drop(&mut self, sender: &Address, nonce: &U256) -> Option<TransactionOrder> {
 		if let Some(tx_order) = self.by_address.remove(sender, nonce) {
 			self.by_priority.remove(&tx_order);
+			self.gas = self.gas - tx_order.gas;
+			self.note_removed(sender, &tx_order);
 			assert_eq!(self.by_priority.len(), self.by_address.len());
 			return Some(tx_order);
 		}
@@ -293,6 +346,39 @@ impl TransactionSet {
 	fn clear(&mut self) {
 		self.by_priority.clear();
 		self.by_address.clear();
+		self.gas = U256::zero();
+		self.external_by_sender.clear();
+		self.senders_over_limit = 0;
+	}
+
+	/// Counts `order` of `sender` which has just been added to this set.
+	fn note_inserted(&mut self, sender: &Address, order: &TransactionOrder) {
+		if order.origin == TransactionOrigin::Local {
+			return;
+		}
+		let count = self.external_by_sender.entry(*sender).or_insert(0);
+		*count += 1;
+		if *count == self.per_sender_limit + 1 {
+			self.senders_over_limit += 1;
+		}
+	}
+
+	/// Stops counting `order` of `sender` which has just been removed from this set.
+	fn note_removed(&mut self, sender: &Address, order: &TransactionOrder) {
+		if order.origin == TransactionOrigin::Local {
+			return;
+		}
+		let count = {
+			let count = self.external_by_sender.get_mut(sender).expect("Every non-local transaction in the set is counted; qed");
+			if *count == self.per_sender_limit + 1 {
+				self.senders_over_limit -= 1;
+			}
+			*count -= 1;
+			*count
+		};
+		if count == 0 {
+			self.external_by_sender.remove(sender);
+		}
 	}
 
 	/// Sets new limit for number of transactions in this `TransactionSet`.
@@ -300,6 +386,13 @@ impl TransactionSet {
 	fn set_limit(&mut self, limit: usize) {
 		self.limit = limit;
 	}
+
+	/// Sets new limit for number of transactions from a single sender in this `TransactionSet`.
+	/// Note the limit is not applied (no transactions are removed) by calling this method.
+	fn set_per_sender_limit(&mut self, limit: usize) {
+		self.per_sender_limit = limit;
+		self.senders_over_limit = self.external_by_sender.values().filter(|count| **count > limit).count();
+	}
 }
 
 #[derive(Debug)]
@@ -309,6 +402,40 @@ pub struct TransactionQueueStatus {
 	pub pending: usize,
 	/// Number of future transactions (waiting for transactions with lower nonces first)
 	pub future: usize,
+	/// Total gas of pending transactions
+	pub pending_gas: U256,
+	/// Total gas of future transactions
+	pub future_gas: U256,
+	/// Number of transactions evicted from the queue because of limits
+	pub evicted: usize,
+}
+
+/// Reason why a transaction is kept in `future` instead of being ready to go to block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FutureReason {
+	/// Transactions with lower nonces from the same sender are missing.
+	NonceGap {
+		/// Next nonce expected from the sender
+		expected: U256,
+	},
+	/// Sender doesn't have enough funds to pay for this transaction.
+	InsufficientBalance {
+		/// Senders balance
+		balance: U256,
+		/// Transaction cost
+		cost: U256,
+	},
+}
+
+/// Transaction kept in the queue, with the details of its position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedTransaction {
+	/// The transaction
+	pub transaction: SignedTransaction,
+	/// Origin of the transaction
+	pub origin: TransactionOrigin,
+	/// Why the transaction is in `future` (`None` if it's ready to go to block)
+	pub future: Option<FutureReason>,
 }
 
 /// Details of account
@@ -323,14 +450,21 @@ pub struct AccountDetails {
 /// Transactions with `gas > (gas_limit + gas_limit * Factor(in percents))` are not imported to the queue.
 const GAS_LIMIT_HYSTERESIS: usize = 10; // %
 
+/// Default gas price increase required to replace a transaction with the same `(sender, nonce)`.
+pub const DEFAULT_REPLACE_BUMP: usize = 10; // %
+
 /// `TransactionQueue` implementation
 pub struct TransactionQueue {
 	/// Gas Price threshold for transactions that can be imported to this queue (defaults to 0)
 	minimal_gas_price: U256,
+	/// Gas price increase (in percents) a transaction needs to replace one with the same `(sender, nonce)`
+	replace_bump: usize,
 	/// The maximum amount of gas any individual transaction may use.
 	tx_gas_limit: U256,
 	/// Current gas limit (block gas limit * factor). Transactions above the limit will not be accepted (default to !0)
 	gas_limit: U256,
+	/// Maximum total gas of transactions in `current` and `future` together (default to !0)
+	total_gas_limit: U256,
 	/// Priority queue for transactions that can go to block
 	current: TransactionSet,
 	/// Priority queue for transactions that has been received but are not yet valid to go to block
@@ -359,18 +493,30 @@ impl TransactionQueue {
 			by_priority: BTreeSet::new(),
 			by_address: Table::new(),
 			limit: limit,
+			per_sender_limit: limit,
+			gas: U256::zero(),
+			external_by_sender: HashMap::new(),
+			senders_over_limit: 0,
+			evicted: 0,
 		};
 
 		let future = TransactionSet {
 			by_priority: BTreeSet::new(),
 			by_address: Table::new(),
 			limit: limit,
+			per_sender_limit: limit,
+			gas: U256::zero(),
+			external_by_sender: HashMap::new(),
+			senders_over_limit: 0,
+			evicted: 0,
 		};
 
 		TransactionQueue {
 			minimal_gas_price: U256::zero(),
+			replace_bump: DEFAULT_REPLACE_BUMP,
 			tx_gas_limit: tx_gas_limit,
 			gas_limit: !U256::zero(),
+			total_gas_limit: !U256::zero(),
 			current: current,
 			future: future,
 			by_hash: HashMap::new(),
@@ -383,8 +529,7 @@ impl TransactionQueue {
 		self.current.set_limit(limit);
 		self.future.set_limit(limit);
 		// And ensure the limits
-		self.current.enforce_limit(&mut self.by_hash);
-		self.future.enforce_limit(&mut self.by_hash);
+		self.enforce_limits();
 	}
 
 	/// Returns current limit of transactions in the queue.
@@ -392,6 +537,49 @@ impl TransactionQueue {
 		self.current.limit
 	}
 
+	/// Set the new limit for number of transactions from a single sender in `current` and `future` queue.
+	pub fn set_per_sender_limit(&mut self, limit: usize) {
+		self.current.set_per_sender_limit(limit);
+		self.future.set_per_sender_limit(limit);
+		self.enforce_limits();
+	}
+
+	/// Returns current limit of transactions from a single sender in the queue.
+	pub fn per_sender_limit(&self) -> usize {
+		self.current.per_sender_limit
+	}
+
+	/// Set the new limit for total gas of transactions in `current` and `future` queue together.
+	pub fn set_total_gas_limit(&mut self, gas_limit: U256) {
+		self.total_gas_limit = gas_limit;
+		self.enforce_limits();
+	}
+
+	/// Returns current limit for total gas of transactions in the queue.
+	pub fn total_gas_limit(&self) -> U256 {
+		self.total_gas_limit
+	}
+
+	fn enforce_limits(&mut self) {
+		let removed = self.enforce_current_limit();
+		self.update_last_nonces(&removed);
+		self.enforce_future_limit();
+	}
+
+	/// Enforces limits of `current`, which may use the whole `total_gas_limit`.
+	fn enforce_current_limit(&mut self) -> Option<HashMap<Address, U256>> {
+		self.current.enforce_limit(self.total_gas_limit, &mut self.by_hash)
+	}
+
+	/// Enforces limits of `future`, which may only use gas left over by `current`.
+	fn enforce_future_limit(&mut self) -> Option<HashMap<Address, U256>> {
+		let gas_limit = match self.total_gas_limit.overflowing_sub(self.current.gas) {
+			(_, true) => U256::zero(),
+			(val, false) => val,
+		};
+		self.future.enforce_limit(gas_limit, &mut self.by_hash)
+	}
+
 	/// Get the minimal gas price.
 	pub fn minimal_gas_price(&self) -> &U256 {
 		&self.minimal_gas_price
@@ -403,6 +591,17 @@ impl TransactionQueue {
 		self.minimal_gas_price = min_gas_price;
 	}
 
+	/// Get the gas price increase (in percents) required to replace a transaction.
+	pub fn replace_bump(&self) -> usize {
+		self.replace_bump
+	}
+
+	/// Sets the gas price increase (in percents) a transaction needs to replace
+	/// a queued one with the same sender and nonce.
+	pub fn set_replace_bump(&mut self, percent: usize) {
+		self.replace_bump = percent;
+	}
+
 	/// Sets new gas limit. Transactions with gas slightly (`GAS_LIMIT_HYSTERESIS`) above the limit won't be imported.
 	/// Any transaction already imported to the queue is not affected.
 	pub fn set_gas_limit(&mut self, gas_limit: U256) {
@@ -425,6 +624,9 @@ impl TransactionQueue {
 		TransactionQueueStatus {
 			pending: self.current.by_priority.len(),
 			future: self.future.by_priority.len(),
+			pending_gas: self.current.gas,
+			future_gas: self.future.gas,
+			evicted: self.current.evicted + self.future.evicted,
 		}
 	}
 
@@ -583,7 +785,7 @@ impl TransactionQueue {
 				self.by_hash.remove(&order.hash).expect("All transactions in `future` are also in `by_hash`");
 			}
 		}
-		self.future.enforce_limit(&mut self.by_hash);
+		self.enforce_future_limit();
 	}
 
 	/// Returns top transactions from the queue ordered by priority.
@@ -595,6 +797,32 @@ impl TransactionQueue {
 			.collect()
 	}
 
+	/// Returns all transactions from the queue (`current` first, then `future`) ordered by priority,
+	/// together with their origin and the reason why `future` ones can't go to block yet.
+	pub fn queued_transactions(&self) -> Vec<QueuedTransaction> {
+		let current = self.current.by_priority.iter().map(|order| (order, false));
+		let future = self.future.by_priority.iter().map(|order| (order, true));
+		current.chain(future)
+			.map(|(order, is_future)| {
+				let tx = self.by_hash.get(&order.hash).expect("All transactions in `current` and `future` are always included in `by_hash`");
+				let future = match is_future {
+					true => {
+						let sender = tx.sender();
+						// `nonce_height` is relative to the state nonce of the sender
+						let expected = self.last_nonces.get(&sender).map_or(tx.nonce() - order.nonce_height, |n| *n + U256::one());
+						Some(FutureReason::NonceGap { expected: expected })
+					},
+					false => None,
+				};
+				QueuedTransaction {
+					transaction: tx.transaction.clone(),
+					origin: tx.origin,
+					future: future,
+				}
+			})
+			.collect()
+	}
+
 	/// Returns hashes of all transactions from current, ordered by priority.
 	pub fn pending_hashes(&self) -> Vec<H256> {
 		self.current.by_priority
@@ -630,23 +858,13 @@ impl TransactionQueue {
 	/// (because nonce matches).
 	fn move_matching_future_to_current(&mut self, address: Address, mut current_nonce: U256, first_nonce: U256) {
 		let mut update_last_nonce_to = None;
-		{
-			let by_nonce = self.future.by_address.row_mut(&address);
-			if let None = by_nonce {
-				return;
-			}
-			let mut by_nonce = by_nonce.unwrap();
-			while let Some(order) = by_nonce.remove(&current_nonce) {
-				// remove also from priority and hash
-				self.future.by_priority.remove(&order);
-				// Put to current
-				let order = order.update_height(current_nonce, first_nonce);
-				self.current.insert(address, current_nonce, order);
-				update_last_nonce_to = Some(current_nonce);
-				current_nonce = current_nonce + U256::one();
-			}
+		while let Some(order) = self.future.drop(&address, &current_nonce) {
+			// Put to current
+			let order = order.update_height(current_nonce, first_nonce);
+			self.current.insert(address, current_nonce, order);
+			update_last_nonce_to = Some(current_nonce);
+			current_nonce = current_nonce + U256::one();
 		}
-		self.future.by_address.clear_if_empty(&address);
 		if let Some(x) = update_last_nonce_to {
 			// Update last inserted nonce
 			self.last_nonces.insert(address, x);
@@ -660,7 +878,7 @@ impl TransactionQueue {
 	/// this.
 	///
 	/// It ignores transactions that has already been imported (same `hash`) and replaces the transaction
-	/// iff `(address, nonce)` is the same but `gas_price` is higher.
+	/// iff `(address, nonce)` is the same but `gas_price` is higher by at least `replace_bump` percent.
 	///
 	/// Returns `true` when transaction was imported successfuly
 	fn import_tx(&mut self, tx: VerifiedTransaction, state_nonce: U256) -> Result<TransactionImportResult, TransactionError> {
@@ -673,6 +891,7 @@ impl TransactionQueue {
 
 		let address = tx.sender();
 		let nonce = tx.nonce();
+		let gas_price = tx.transaction.gas_price;
 
 		let next_nonce = self.last_nonces
 			.get(&address)
@@ -691,12 +910,12 @@ impl TransactionQueue {
 			// Update nonces of transactions in future (remove old transactions)
 			self.update_future(&address, state_nonce);
 			// Insert transaction (or replace old one with lower gas price)
-			try!(check_too_cheap(Self::replace_transaction(tx, state_nonce, &mut self.future, &mut self.by_hash)));
+			try!(Self::replace_transaction(tx, state_nonce, self.replace_bump, &mut self.future, &mut self.by_hash));
 			// Return an error if this transaction is not imported because of limit.
-			try!(check_if_removed(&address, &nonce, self.future.enforce_limit(&mut self.by_hash)));
+			try!(check_if_removed(&address, &nonce, self.enforce_future_limit()));
 			return Ok(TransactionImportResult::Future);
 		}
-		try!(check_too_cheap(Self::replace_transaction(tx, state_nonce, &mut self.current, &mut self.by_hash)));
+		try!(Self::replace_transaction(tx, state_nonce, self.replace_bump, &mut self.current, &mut self.by_hash));
 		// Keep track of highest nonce stored in current
 		let new_max = self.last_nonces.get(&address).map_or(nonce, |n| cmp::max(nonce, *n));
 		self.last_nonces.insert(address, new_max);
@@ -710,13 +929,20 @@ impl TransactionQueue {
 			// Let's insert that transaction to current (if it has higher gas_price)
 			let future_tx = self.by_hash.remove(&order.hash).expect("All transactions in `future` are always in `by_hash`.");
 			// if transaction in `current` (then one we are importing) is replaced it means that it has to low gas_price
-			try!(check_too_cheap(!Self::replace_transaction(future_tx, state_nonce, &mut self.current, &mut self.by_hash)));
+			if Self::replace_transaction(future_tx, state_nonce, self.replace_bump, &mut self.current, &mut self.by_hash).is_ok() {
+				return Err(TransactionError::TooCheapToReplace {
+					minimal: replacement_gas_price(order.gas_price, self.replace_bump),
+					got: gas_price,
+				});
+			}
 		}
 
 		// Also enforce the limit
-		let removed = self.current.enforce_limit(&mut self.by_hash);
+		let removed = self.enforce_current_limit();
 		// If some transaction were removed because of limit we need to update last_nonces also.
 		self.update_last_nonces(&removed);
+		// Gas used by `current` is not available to `future` anymore.
+		self.enforce_future_limit();
 		// Trigger error if the transaction we are importing was removed.
 		try!(check_if_removed(&address, &nonce, removed));
 
@@ -739,12 +965,13 @@ impl TransactionQueue {
 
 	/// Replaces transaction in given set (could be `future` or `current`).
 	///
-	/// If there is already transaction with same `(sender, nonce)` it will be replaced iff `gas_price` is higher.
+	/// If there is already transaction with same `(sender, nonce)` it will be replaced iff `gas_price` is higher
+	/// by at least `replace_bump` percent.
 	/// One of the transactions is dropped from set and also removed from queue entirely (from `by_hash`).
 	///
-	/// Returns `true` if transaction actually got to the queue (`false` if there was already a transaction with higher
-	/// gas_price)
-	fn replace_transaction(tx: VerifiedTransaction, base_nonce: U256, set: &mut TransactionSet, by_hash: &mut HashMap<H256, VerifiedTransaction>) -> bool {
+	/// Returns `TooCheapToReplace` error if transaction didn't get to the queue (there was already a transaction
+	/// with too high gas_price)
+	fn replace_transaction(tx: VerifiedTransaction, base_nonce: U256, replace_bump: usize, set: &mut TransactionSet, by_hash: &mut HashMap<H256, VerifiedTransaction>) -> Result<(), TransactionError> {
 		let order = TransactionOrder::for_transaction(&tx, base_nonce);
 		let hash = tx.hash();
 		let address = tx.sender();
@@ -756,30 +983,41 @@ impl TransactionQueue {
 
 		if let Some(old) = set.insert(address, nonce, order.clone()) {
 			// There was already transaction in queue. Let's check which one should stay
-			let old_fee = old.gas_price;
+			let min_fee = replacement_gas_price(old.gas_price, replace_bump);
 			let new_fee = order.gas_price;
-			if old_fee.cmp(&new_fee) == Ordering::Greater {
+			if min_fee.cmp(&new_fee) == Ordering::Greater {
 				// Put back old transaction since it has greater priority (higher gas_price)
 				set.insert(address, nonce, old);
 				// and remove new one
 				by_hash.remove(&hash).expect("The hash has been just inserted and no other line is altering `by_hash`.");
-				false
+				Err(TransactionError::TooCheapToReplace {
+					minimal: min_fee,
+					got: new_fee,
+				})
 			} else {
 				// Make sure we remove old transaction entirely
 				by_hash.remove(&old.hash).expect("The hash is coming from `future` so it has to be in `by_hash`.");
-				true
+				Ok(())
 			}
 		} else {
-			true
+			Ok(())
 		}
 	}
 }
 
-fn check_too_cheap(is_in: bool) -> Result<(), TransactionError> {
-	if is_in {
-		Ok(())
-	} else {
-		Err(TransactionError::TooCheapToReplace)
+/// Returns the lowest gas price that replaces a transaction with `gas_price`, given required increase in percents.
+/// Any non-zero `bump` requires the gas price to grow by at least one.
+fn replacement_gas_price(gas_price: U256, bump: usize) -> U256 {
+	if bump == 0 {
+		return gas_price;
+	}
+	let increase = match gas_price.overflowing_mul(U256::from(bump)) {
+		(_, true) => !U256::zero(),
+		(val, false) => cmp::max(val / U256::from(100), U256::one()),
+	};
+	match gas_price.overflowing_add(increase) {
+		(_, true) => !U256::zero(),
+		(val, false) => val,
 	}
 }
 
@@ -804,7 +1042,7 @@ mod test {
 	use transaction::*;
 	use error::{Error, TransactionError};
 	use super::*;
-	use super::{TransactionSet, TransactionOrder, VerifiedTransaction};
+	use super::{TransactionSet, TransactionOrder, VerifiedTransaction, replacement_gas_price};
 	use client::TransactionImportResult;
 
 	fn unwrap_tx_err(err: Result<TransactionImportResult, Error>) -> TransactionError {
@@ -875,7 +1113,12 @@ mod test {
 		let mut set = TransactionSet {
 			by_priority: BTreeSet::new(),
 			by_address: Table::new(),
-			limit: 1
+			limit: 1,
+			per_sender_limit: 1,
+			gas: U256::zero(),
+			external_by_sender: HashMap::new(),
+			senders_over_limit: 0,
+			evicted: 0,
 		};
 		let (tx1, tx2) = new_txs(U256::from(1));
 		let tx1 = VerifiedTransaction::new(tx1, TransactionOrigin::External).unwrap();
@@ -897,7 +1140,7 @@ mod test {
 		assert_eq!(set.by_address.len(), 2);
 
 		// when
-		set.enforce_limit(&mut by_hash);
+		set.enforce_limit(!U256::zero(), &mut by_hash);
 
 		// then
 		assert_eq!(by_hash.len(), 1);
@@ -914,7 +1157,12 @@ mod test {
 		let mut set = TransactionSet {
 			by_priority: BTreeSet::new(),
 			by_address: Table::new(),
-			limit: 1
+			limit: 1,
+			per_sender_limit: 1,
+			gas: U256::zero(),
+			external_by_sender: HashMap::new(),
+			senders_over_limit: 0,
+			evicted: 0,
 		};
 		// Create two transactions with same nonce
 		// (same hash)
@@ -1668,4 +1916,236 @@ mod test {
 		assert_eq!(txq.current.by_priority.len(), 3);
 	}
 
+	fn new_replacement_txs(gas_price: U256, replacement_gas_price: U256) -> (SignedTransaction, SignedTransaction) {
+		let keypair = KeyPair::create().unwrap();
+		let secret = &keypair.secret();
+		let mut tx = new_unsigned_tx(default_nonce_val());
+		tx.gas_price = gas_price;
+		let mut tx2 = new_unsigned_tx(default_nonce_val());
+		tx2.gas_price = replacement_gas_price;
+
+		(tx.sign(secret), tx2.sign(secret))
+	}
+
+	#[test]
+	fn should_not_replace_transaction_without_sufficient_bump() {
+		// given
+		let mut txq = TransactionQueue::new();
+		let (tx, tx2) = new_replacement_txs(U256::from(100), U256::from(109));
+		txq.add(tx.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let res = txq.add(tx2, &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(unwrap_tx_err(res), TransactionError::TooCheapToReplace { minimal: U256::from(110), got: U256::from(109) });
+		assert_eq!(txq.top_transactions(), vec![tx]);
+	}
+
+	#[test]
+	fn should_replace_transaction_with_sufficient_bump() {
+		// given
+		let mut txq = TransactionQueue::new();
+		let (tx, tx2) = new_replacement_txs(U256::from(100), U256::from(110));
+		txq.add(tx, &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let res = txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(res.unwrap(), TransactionImportResult::Current);
+		assert_eq!(txq.top_transactions(), vec![tx2]);
+	}
+
+	#[test]
+	fn should_replace_transaction_with_same_gas_price_if_no_bump_required() {
+		// given
+		let mut txq = TransactionQueue::new();
+		txq.set_replace_bump(0);
+		let (tx, tx2) = new_replacement_txs(U256::from(100), U256::from(100));
+		txq.add(tx, &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let res = txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(res.unwrap(), TransactionImportResult::Current);
+		assert_eq!(txq.top_transactions(), vec![tx2]);
+	}
+
+	#[test]
+	fn should_not_promote_future_transaction_without_sufficient_bump() {
+		// given
+		let mut txq = TransactionQueue::new();
+		let (tx, tx2) = new_replacement_txs(U256::from(105), U256::from(100));
+		let previous_nonce = |a: &Address| AccountDetails { nonce: default_nonce(a).nonce - U256::one(), balance: !U256::zero() };
+		assert_eq!(txq.add(tx, &previous_nonce, TransactionOrigin::External).unwrap(), TransactionImportResult::Future);
+
+		// when
+		let res = txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(res.unwrap(), TransactionImportResult::Current);
+		assert_eq!(txq.top_transactions(), vec![tx2]);
+		assert_eq!(txq.status().future, 0);
+	}
+
+	#[test]
+	fn should_return_queued_transactions_with_future_reason() {
+		// given
+		let mut txq = TransactionQueue::new();
+		let (tx1, tx2) = new_txs(U256::from(2));
+		txq.add(tx1.clone(), &default_nonce, TransactionOrigin::Local).unwrap();
+		txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let queued = txq.queued_transactions();
+
+		// then
+		assert_eq!(queued, vec![
+			QueuedTransaction { transaction: tx1, origin: TransactionOrigin::Local, future: None },
+			QueuedTransaction {
+				transaction: tx2,
+				origin: TransactionOrigin::External,
+				future: Some(FutureReason::NonceGap { expected: default_nonce_val() + U256::one() }),
+			},
+		]);
+	}
+
+	#[test]
+	fn should_limit_transactions_per_sender() {
+		// given
+		let mut txq = TransactionQueue::new();
+		txq.set_per_sender_limit(2);
+		let keypair = KeyPair::create().unwrap();
+		let txs: Vec<_> = (0..3)
+			.map(|i| new_unsigned_tx(default_nonce_val() + U256::from(i)).sign(keypair.secret()))
+			.collect();
+		txq.add(txs[0].clone(), &default_nonce, TransactionOrigin::External).unwrap();
+		txq.add(txs[1].clone(), &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let res = txq.add(txs[2].clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(unwrap_tx_err(res), TransactionError::LimitReached);
+		assert_eq!(txq.top_transactions(), vec![txs[0].clone(), txs[1].clone()]);
+		assert_eq!(txq.status().evicted, 1);
+	}
+
+	#[test]
+	fn should_apply_lowered_per_sender_limit_on_next_import() {
+		// given
+		let mut txq = TransactionQueue::new();
+		let keypair = KeyPair::create().unwrap();
+		let txs: Vec<_> = (0..3)
+			.map(|i| new_unsigned_tx(default_nonce_val() + U256::from(i)).sign(keypair.secret()))
+			.collect();
+		for tx in &txs {
+			txq.add(tx.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+		}
+		txq.set_per_sender_limit(2);
+
+		// when
+		txq.add(new_tx(), &default_nonce, TransactionOrigin::External).unwrap();
+
+		// then
+		assert_eq!(txq.status().pending, 3);
+		assert_eq!(txq.status().evicted, 1);
+		assert!(!txq.top_transactions().contains(&txs[2]));
+	}
+
+	#[test]
+	fn should_evict_transactions_of_biggest_sender_first() {
+		// given
+		let mut txq = TransactionQueue::with_limits(3, !U256::zero());
+		let (tx1, tx2) = new_txs(U256::one());
+		let tx3 = new_tx();
+		let tx4 = new_tx();
+		txq.add(tx1.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+		txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+		txq.add(tx3.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let res = txq.add(tx4.clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(res.unwrap(), TransactionImportResult::Current);
+		assert_eq!(txq.status().pending, 3);
+		assert!(txq.find(&tx2.hash()).is_none());
+		assert_eq!(txq.last_nonce(&tx1.sender().unwrap()), Some(tx1.nonce));
+		assert_eq!(txq.status().evicted, 1);
+	}
+
+	#[test]
+	fn should_never_evict_local_transactions() {
+		// given
+		let mut txq = TransactionQueue::with_limits(1, !U256::zero());
+		let (tx1, tx2) = new_txs(U256::one());
+		txq.add(tx1.clone(), &default_nonce, TransactionOrigin::Local).unwrap();
+		txq.add(tx2.clone(), &default_nonce, TransactionOrigin::Local).unwrap();
+		assert_eq!(txq.status().pending, 2);
+
+		// when
+		let res = txq.add(new_tx(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(unwrap_tx_err(res), TransactionError::LimitReached);
+		assert_eq!(txq.top_transactions(), vec![tx1, tx2]);
+	}
+
+	#[test]
+	fn should_evict_cheapest_transactions_above_total_gas_limit() {
+		// given
+		let mut txq = TransactionQueue::new();
+		txq.set_total_gas_limit(U256::from(150_000));
+		let tx1 = new_tx();
+		let tx2 = {
+			let keypair = KeyPair::create().unwrap();
+			let mut tx = new_unsigned_tx(default_nonce_val());
+			tx.gas_price = U256::from(2);
+			tx.sign(keypair.secret())
+		};
+		txq.add(tx1.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+
+		// when
+		let res = txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(res.unwrap(), TransactionImportResult::Current);
+		assert_eq!(txq.top_transactions(), vec![tx2]);
+		let status = txq.status();
+		assert_eq!(status.pending_gas, U256::from(100_000));
+		assert_eq!(status.future_gas, U256::zero());
+		assert_eq!(status.evicted, 1);
+	}
+
+	#[test]
+	fn should_apply_total_gas_limit_to_current_and_future_together() {
+		// given
+		let mut txq = TransactionQueue::new();
+		txq.set_total_gas_limit(U256::from(150_000));
+		let (tx1, tx2) = new_txs(U256::from(2));
+		txq.add(tx2.clone(), &default_nonce, TransactionOrigin::External).unwrap();
+		assert_eq!(txq.status().future_gas, U256::from(100_000));
+
+		// when
+		let res = txq.add(tx1.clone(), &default_nonce, TransactionOrigin::External);
+
+		// then
+		assert_eq!(res.unwrap(), TransactionImportResult::Current);
+		let status = txq.status();
+		assert_eq!(status.pending, 1);
+		assert_eq!(status.future, 0);
+		assert_eq!(status.pending_gas, U256::from(100_000));
+		assert_eq!(status.future_gas, U256::zero());
+		assert_eq!(status.evicted, 1);
+	}
+
+	#[test]
+	fn should_require_bump_of_at_least_one_for_cheap_transactions() {
+		assert_eq!(replacement_gas_price(U256::one(), 10), U256::from(2));
+		assert_eq!(replacement_gas_price(U256::one(), 0), U256::one());
+		assert_eq!(replacement_gas_price(!U256::zero(), 10), !U256::zero());
+	}
 }
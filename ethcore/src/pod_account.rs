@@ -18,6 +18,7 @@ use util::*;
 use account::*;
 use account_db::*;
 use ethjson;
+use serde_json;
 use types::account_diff::*;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -62,13 +63,27 @@ impl PodAccount {
 		stream.out()
 	}
 
-	/// Place additional data into given hash DB.
-	pub fn insert_additional(&self, db: &mut AccountDBMut) {
+	/// Returns the JSON representation of this account, as accepted in the `accounts` of a chain spec.
+	pub fn to_json(&self) -> String {
+		let account = ethjson::spec::Account {
+			builtin: None,
+			balance: Some(ethjson::uint::Uint(self.balance)),
+			nonce: Some(ethjson::uint::Uint(self.nonce)),
+			code: Some(ethjson::bytes::Bytes::new(self.code.clone())),
+			storage: Some(self.storage.iter().map(|(k, v)| {
+				(ethjson::uint::Uint(U256::from(k.as_slice())), ethjson::uint::Uint(U256::from(v.as_slice())))
+			}).collect()),
+		};
+		serde_json::to_string(&account).expect("serialization of a spec account cannot fail; qed")
+	}
+
+	/// Place additional data into given hash DB, building the storage trie with `trie_factory`.
+	pub fn insert_additional(&self, db: &mut AccountDBMut, trie_factory: &TrieFactory) {
 		if !self.code.is_empty() {
 			db.insert(&self.code);
 		}
 		let mut r = H256::new();
-		let mut t = SecTrieDBMut::new(db, &mut r);
+		let mut t = trie_factory.create(db, &mut r);
 		for (k, v) in &self.storage {
 			t.insert(k, &encode(&U256::from(v.as_slice())));
 		}
@@ -95,8 +110,12 @@ impl From<ethjson::spec::Account> for PodAccount {
 		PodAccount {
 			balance: a.balance.map_or_else(U256::zero, Into::into),
 			nonce: a.nonce.map_or_else(U256::zero, Into::into),
-			code: vec![],
-			storage: BTreeMap::new()
+			code: a.code.map_or_else(Vec::new, Into::into),
+			storage: a.storage.map_or_else(BTreeMap::new, |s| s.into_iter().map(|(key, value)| {
+				let key: U256 = key.into();
+				let value: U256 = value.into();
+				(H256::from(key), H256::from(value))
+			}).collect()),
 		}
 	}
 }
@@ -217,4 +236,15 @@ mod test {
 			],
 		}));
 	}
+
+	#[test]
+	fn to_json() {
+		let a = PodAccount {
+			balance: 69.into(),
+			nonce: 0.into(),
+			code: vec![0x60, 0x01],
+			storage: map_into![1 => 2]
+		};
+		assert_eq!(a.to_json(), "{\"balance\":\"0x45\",\"nonce\":\"0x0\",\"code\":\"0x6001\",\"storage\":{\"0x1\":\"0x2\"}}");
+	}
 }
@@ -0,0 +1,318 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! On-chain registry of zkSNARK verification keys.
+//!
+//! The registry lives at a system address configured in the chain spec and accepts
+//! two ABI-encoded calls:
+//!
+//! - `register(bytes vk)` stores the verification key in the registry's storage and
+//!   returns its hash.
+//! - `verify(bytes32 vkHash, bytes proof, bytes primaryInput)` looks up a registered key
+//!   and verifies the proof using the `zkSNARK` builtin configured as verifier.
+//!
+//! A key is stored with its length under its hash and its contents in consecutive
+//! 32-byte words starting at `sha3(hash)`, in the storage of the account the call executes in
+//! (the registry itself, or the caller for `DELEGATECALL` and `CALLCODE`).
+
+use common::*;
+use engine::Engine;
+use state::State;
+use ethjson;
+use ethabi;
+use ethabi::Token;
+use ethabi::spec::ParamType;
+
+/// Name under which calls to the registry are traced.
+pub const REGISTRY_NAME: &'static str = "zkSNARKRegistry";
+
+/// zkSNARK verification key registry configuration.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SnarkRegistry {
+	/// Address of the registry code.
+	pub address: Address,
+	/// Address of the `zkSNARK` builtin used to verify proofs against registered keys.
+	pub verifier: Address,
+	/// Number of the first block in which the registry is active; before it the address is a plain account.
+	pub activate_at: BlockNumber,
+}
+
+impl From<ethjson::spec::SnarkRegistry> for SnarkRegistry {
+	fn from(r: ethjson::spec::SnarkRegistry) -> Self {
+		SnarkRegistry {
+			address: r.address.into(),
+			verifier: r.verifier.into(),
+			activate_at: r.activate_at.map_or(0, Into::into),
+		}
+	}
+}
+
+/// Call to the registry decoded from its input.
+#[derive(Debug, PartialEq)]
+enum RegistryCall {
+	Register(Bytes),
+	Verify {
+		vk_hash: H256,
+		proof: Bytes,
+		primary_input: Bytes,
+	},
+}
+
+fn selector(signature: &str) -> Bytes {
+	signature.sha3()[..4].to_vec()
+}
+
+fn words(len: usize) -> usize {
+	(len + 31) / 32
+}
+
+impl RegistryCall {
+	fn decode(input: &[u8]) -> Result<Self, BuiltinError> {
+		if input.len() < 4 {
+			return Err(BuiltinError("input too short"));
+		}
+		let (sel, data) = input.split_at(4);
+		if sel == &selector("register(bytes)")[..] {
+			let tokens = try!(ethabi::Decoder::decode(&[ParamType::Bytes], data.to_vec()).map_err(|_| BuiltinError("invalid ABI encoding")));
+			match tokens.into_iter().next() {
+				Some(Token::Bytes(ref vk)) if vk.is_empty() => Err(BuiltinError("empty verification key")),
+				Some(Token::Bytes(vk)) => Ok(RegistryCall::Register(vk)),
+				_ => Err(BuiltinError("invalid ABI encoding")),
+			}
+		} else if sel == &selector("verify(bytes32,bytes,bytes)")[..] {
+			let types = [ParamType::FixedBytes(32), ParamType::Bytes, ParamType::Bytes];
+			let tokens = try!(ethabi::Decoder::decode(&types, data.to_vec()).map_err(|_| BuiltinError("invalid ABI encoding")));
+			let mut tokens = tokens.into_iter();
+			match (tokens.next(), tokens.next(), tokens.next()) {
+				(Some(Token::FixedBytes(vk_hash)), Some(Token::Bytes(proof)), Some(Token::Bytes(primary_input))) => Ok(RegistryCall::Verify {
+					vk_hash: H256::from_slice(&vk_hash),
+					proof: proof,
+					primary_input: primary_input,
+				}),
+				_ => Err(BuiltinError("invalid ABI encoding")),
+			}
+		} else {
+			Err(BuiltinError("unknown function"))
+		}
+	}
+}
+
+impl SnarkRegistry {
+	/// Whether the registry is active in block `number`.
+	pub fn is_active(&self, number: BlockNumber) -> bool {
+		number >= self.activate_at
+	}
+
+	fn chunk_key(vk_hash: &H256, index: usize) -> H256 {
+		H256::from(U256::from(vk_hash.sha3()) + U256::from(index))
+	}
+
+	fn stored_len(state: &State, storage: &Address, vk_hash: &H256) -> usize {
+		U256::from(state.storage_at(storage, vk_hash)).low_u64() as usize
+	}
+
+	/// Store verification key `vk` in the storage of `storage` and return its hash.
+	pub fn store(&self, state: &mut State, storage: &Address, vk: &[u8]) -> H256 {
+		let vk_hash = vk.sha3();
+		state.set_storage(storage, vk_hash.clone(), H256::from(U256::from(vk.len())));
+		for (i, chunk) in vk.chunks(32).enumerate() {
+			let mut word = H256::new();
+			copy_to(chunk, word.as_slice_mut());
+			state.set_storage(storage, Self::chunk_key(&vk_hash, i), word);
+		}
+		vk_hash
+	}
+
+	/// Load verification key with hash `vk_hash` from the storage of `storage`, if registered.
+	pub fn load(&self, state: &State, storage: &Address, vk_hash: &H256) -> Option<Bytes> {
+		let len = Self::stored_len(state, storage, vk_hash);
+		if len == 0 {
+			return None;
+		}
+		let mut vk = Vec::with_capacity(words(len) * 32);
+		for i in 0..words(len) {
+			vk.extend_from_slice(&state.storage_at(storage, &Self::chunk_key(vk_hash, i)));
+		}
+		vk.truncate(len);
+		Some(vk)
+	}
+
+	/// Input for the verifier builtin equivalent to verifying `proof` against `vk` directly.
+	fn verifier_input(vk: &[u8], proof: &[u8], primary_input: &[u8]) -> Bytes {
+		let mut input = vec![0u8; 4];
+		input.extend(ethabi::Encoder::encode(vec![
+			Token::Bytes(vk.to_vec()),
+			Token::Bytes(proof.to_vec()),
+			Token::Bytes(primary_input.to_vec()),
+		]));
+		input
+	}
+
+	/// Flat cost every call to the registry pays at least: a single storage read.
+	/// It has to be paid for before `cost` reads the storage.
+	pub fn base_cost(&self, engine: &Engine, env_info: &EnvInfo) -> U256 {
+		U256::from(engine.schedule(env_info).sload_gas)
+	}
+
+	/// Gas cost of calling the registry with `input`, with keys kept in the storage of `storage`.
+	///
+	/// Registering costs hashing the key plus a storage write per word. Verifying costs a
+	/// storage read per word of the key plus the verifier's own cost for the key and proof.
+	/// Input which cannot be decoded is charged the base cost.
+	pub fn cost(&self, state: &State, engine: &Engine, env_info: &EnvInfo, storage: &Address, input: &[u8]) -> U256 {
+		let schedule = engine.schedule(env_info);
+		match RegistryCall::decode(input) {
+			Ok(RegistryCall::Register(vk)) => {
+				let hash_cost = schedule.sha3_gas + schedule.sha3_word_gas * words(vk.len());
+				U256::from(hash_cost) + U256::from(schedule.sstore_set_gas) * U256::from(words(vk.len()) + 1)
+			},
+			Ok(RegistryCall::Verify { vk_hash, proof, primary_input }) => {
+				let lookup_cost = U256::from(schedule.sload_gas) * U256::from(words(Self::stored_len(state, storage, &vk_hash)) + 1);
+				match (self.load(state, storage, &vk_hash), engine.is_builtin(&self.verifier, env_info)) {
+					(Some(vk), true) => lookup_cost + engine.cost_of_builtin(&self.verifier, env_info, &Self::verifier_input(&vk, &proof, &primary_input)),
+					_ => lookup_cost,
+				}
+			},
+			Err(_) => self.base_cost(engine, env_info),
+		}
+	}
+
+	/// Sizes of the arguments decoded from `input`; empty if it could not be decoded.
+	pub fn arg_sizes(&self, input: &[u8]) -> Vec<usize> {
+		match RegistryCall::decode(input) {
+			Ok(RegistryCall::Register(vk)) => vec![vk.len()],
+			Ok(RegistryCall::Verify { proof, primary_input, .. }) => vec![32, proof.len(), primary_input.len()],
+			Err(_) => vec![],
+		}
+	}
+
+	/// Execute a registry call on `input` with keys kept in the storage of `storage`, placing the result into `output`.
+	///
+	/// Registering outputs the key hash. Verifying outputs the verifier's result.
+	pub fn execute(&self, state: &mut State, engine: &Engine, env_info: &EnvInfo, storage: &Address, input: &[u8], output: &mut [u8]) -> BuiltinResult {
+		match try!(RegistryCall::decode(input)) {
+			RegistryCall::Register(vk) => {
+				let vk_hash = self.store(state, storage, &vk);
+				copy_to(&vk_hash, output);
+				Ok(())
+			},
+			RegistryCall::Verify { vk_hash, proof, primary_input } => {
+				if !engine.is_builtin(&self.verifier, env_info) {
+					return Err(BuiltinError("verifier is not a builtin"));
+				}
+				let vk = try!(self.load(state, storage, &vk_hash).ok_or(BuiltinError("unknown verification key")));
+				engine.execute_builtin(&self.verifier, env_info, &Self::verifier_input(&vk, &proof, &primary_input), output)
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use common::*;
+	use tests::helpers::*;
+	use devtools::*;
+	use ethabi;
+	use ethabi::Token;
+	use engine::Engine;
+	use super::*;
+
+	fn register_input(vk: &[u8]) -> Bytes {
+		let mut input = "register(bytes)".sha3()[..4].to_vec();
+		input.extend(ethabi::Encoder::encode(vec![Token::Bytes(vk.to_vec())]));
+		input
+	}
+
+	#[test]
+	fn should_store_and_load_verification_key() {
+		let temp = RandomTempPath::new();
+		let mut state = get_temp_state_in(temp.as_path());
+		let registry = SnarkRegistry { address: 0x100.into(), verifier: 0x5.into(), activate_at: 0 };
+		let vk: Vec<u8> = (0..100u8).collect();
+
+		let vk_hash = registry.store(&mut state, &registry.address, &vk);
+
+		assert_eq!(vk_hash, vk.sha3());
+		assert_eq!(registry.load(&state, &registry.address, &vk_hash), Some(vk));
+		assert_eq!(registry.load(&state, &registry.address, &H256::from(1)), None);
+	}
+
+	#[test]
+	fn should_register_through_call() {
+		let temp = RandomTempPath::new();
+		let mut state = get_temp_state_in(temp.as_path());
+		let engine = TestEngine::new(0);
+		let registry = SnarkRegistry { address: 0x100.into(), verifier: 0x5.into(), activate_at: 0 };
+		let vk = vec![1u8; 40];
+
+		let mut output = [0u8; 32];
+		registry.execute(&mut state, &engine, &EnvInfo::default(), &registry.address, &register_input(&vk), &mut output).unwrap();
+
+		assert_eq!(H256::from_slice(&output), vk.sha3());
+		assert_eq!(registry.load(&state, &registry.address, &vk.sha3()), Some(vk));
+		assert_eq!(registry.arg_sizes(&register_input(&[1u8; 40])), vec![40]);
+	}
+
+	#[test]
+	fn should_reject_unknown_function() {
+		let temp = RandomTempPath::new();
+		let mut state = get_temp_state_in(temp.as_path());
+		let engine = TestEngine::new(0);
+		let registry = SnarkRegistry { address: 0x100.into(), verifier: 0x5.into(), activate_at: 0 };
+
+		let mut output = [0u8; 32];
+		let res = registry.execute(&mut state, &engine, &EnvInfo::default(), &registry.address, &[0u8; 36], &mut output);
+		assert_eq!(res, Err(BuiltinError("unknown function")));
+		assert_eq!(registry.arg_sizes(&[0u8; 36]), Vec::<usize>::new());
+	}
+
+	#[test]
+	fn should_charge_base_cost_for_undecodable_input() {
+		let temp = RandomTempPath::new();
+		let state = get_temp_state_in(temp.as_path());
+		let engine = TestEngine::new(0);
+		let registry = SnarkRegistry { address: 0x100.into(), verifier: 0x5.into(), activate_at: 0 };
+		let schedule = engine.schedule(&EnvInfo::default());
+
+		assert_eq!(registry.base_cost(&engine, &EnvInfo::default()), U256::from(schedule.sload_gas));
+		assert_eq!(registry.cost(&state, &engine, &EnvInfo::default(), &registry.address, &[0u8; 36]), U256::from(schedule.sload_gas));
+		assert_eq!(registry.cost(&state, &engine, &EnvInfo::default(), &registry.address, &[]), U256::from(schedule.sload_gas));
+	}
+
+	#[test]
+	fn should_keep_keys_in_storage_of_calling_context() {
+		let temp = RandomTempPath::new();
+		let mut state = get_temp_state_in(temp.as_path());
+		let engine = TestEngine::new(0);
+		let registry = SnarkRegistry { address: 0x100.into(), verifier: 0x5.into(), activate_at: 0 };
+		let caller = Address::from(0x200);
+		let vk = vec![1u8; 40];
+
+		// as through `DELEGATECALL` from `caller`
+		let mut output = [0u8; 32];
+		registry.execute(&mut state, &engine, &EnvInfo::default(), &caller, &register_input(&vk), &mut output).unwrap();
+
+		assert_eq!(registry.load(&state, &caller, &vk.sha3()), Some(vk.clone()));
+		assert_eq!(registry.load(&state, &registry.address, &vk.sha3()), None);
+	}
+
+	#[test]
+	fn should_activate_at_block() {
+		let registry = SnarkRegistry { address: 0x100.into(), verifier: 0x5.into(), activate_at: 10 };
+		assert!(!registry.is_active(9));
+		assert!(registry.is_active(10));
+	}
+}
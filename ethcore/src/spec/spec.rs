@@ -25,6 +25,8 @@ use super::genesis::Genesis;
 use super::seal::Generic as GenericSeal;
 use ethereum;
 use basic_authority::BasicAuthority;
+use instant_seal::InstantSeal;
+use snark_registry::SnarkRegistry;
 use ethjson;
 
 /// Parameters common to all engines.
@@ -38,6 +40,10 @@ pub struct CommonParams {
 	pub network_id: U256,
 	/// Minimum gas limit.
 	pub min_gas_limit: U256,
+	/// zkSNARK verification key registry.
+	pub zksnark_registry: Option<SnarkRegistry>,
+	/// Number of first block where EIP-155 replay-protected transactions are accepted.
+	pub eip155_transition: BlockNumber,
 }
 
 impl From<ethjson::spec::Params> for CommonParams {
@@ -47,6 +53,8 @@ impl From<ethjson::spec::Params> for CommonParams {
 			maximum_extra_data_size: p.maximum_extra_data_size.into(),
 			network_id: p.network_id.into(),
 			min_gas_limit: p.min_gas_limit.into(),
+			zksnark_registry: p.zksnark_registry.map(Into::into),
+			eip155_transition: p.eip155_transition.map_or(BlockNumber::max_value(), Into::into),
 		}
 	}
 }
@@ -95,13 +103,17 @@ pub struct Spec {
 	genesis_state: PodState,
 }
 
-impl From<ethjson::spec::Spec> for Spec {
-	fn from(s: ethjson::spec::Spec) -> Self {
-		let builtins = s.accounts.builtins().into_iter().map(|p| (p.0.into(), From::from(p.1))).collect();
+impl Spec {
+	/// Create a spec from its json description, resolving builtin contracts through `registry`.
+	pub fn from_json(s: ethjson::spec::Spec, registry: &BuiltinRegistry) -> Result<Self, SpecError> {
+		let mut builtins = BTreeMap::new();
+		for (address, builtin) in s.accounts.builtins() {
+			builtins.insert(address.into(), try!(registry.builtin(builtin)));
+		}
 		let g = Genesis::from(s.genesis);
 		let seal: GenericSeal = g.seal.into();
 		let params = CommonParams::from(s.params);
-		Spec {
+		Ok(Spec {
 			name: s.name.into(),
 			params: params.clone(),
 			engine: Spec::engine(s.engine, params, builtins),
@@ -119,16 +131,15 @@ impl From<ethjson::spec::Spec> for Spec {
 			seal_rlp: seal.rlp,
 			state_root_memo: RwLock::new(g.state_root),
 			genesis_state: From::from(s.accounts)
-		}
+		})
 	}
-}
 
-impl Spec {
 	/// Convert engine spec into a boxed Engine of the right underlying type.
 	/// TODO avoid this hard-coded nastiness - use dynamic-linked plugin framework instead.
 	fn engine(engine_spec: ethjson::spec::Engine, params: CommonParams, builtins: BTreeMap<Address, Builtin>) -> Box<Engine> {
 		match engine_spec {
 			ethjson::spec::Engine::Null => Box::new(NullEngine::new(params, builtins)),
+			ethjson::spec::Engine::InstantSeal => Box::new(InstantSeal::new(params, builtins)),
 			ethjson::spec::Engine::Ethash(ethash) => Box::new(ethereum::Ethash::new(params, From::from(ethash.params), builtins)),
 			ethjson::spec::Engine::BasicAuthority(basic_authority) => Box::new(BasicAuthority::new(params, From::from(basic_authority.params), builtins)),
 		}
@@ -219,25 +230,38 @@ impl Spec {
 
 	/// Ensure that the given state DB has the trie nodes in for the genesis state.
 	pub fn ensure_db_good(&self, db: &mut HashDB) -> bool {
+		self.ensure_db_good_with(db, &TrieFactory::default())
+	}
+
+	/// Ensure that the given state DB has the trie nodes in for the genesis state,
+	/// building the tries with `trie_factory` (so a fat DB also gets the genesis key preimages).
+	pub fn ensure_db_good_with(&self, db: &mut HashDB, trie_factory: &TrieFactory) -> bool {
 		if !db.contains(&self.state_root()) {
 			let mut root = H256::new();
 			{
-				let mut t = SecTrieDBMut::new(db, &mut root);
+				let mut t = trie_factory.create(db, &mut root);
 				for (address, account) in self.genesis_state.get().iter() {
 					t.insert(address.as_slice(), &account.rlp());
 				}
 			}
 			for (address, account) in self.genesis_state.get().iter() {
-				account.insert_additional(&mut AccountDBMut::new(db, address));
+				account.insert_additional(&mut AccountDBMut::new(db, address), trie_factory);
 			}
 			assert!(db.contains(&self.state_root()));
 			true
 		} else { false }
 	}
 
-	/// Loads spec from json file.
+	/// Loads spec from json file, resolving builtin contracts through `registry`.
+	pub fn load_with_registry(reader: &[u8], registry: &BuiltinRegistry) -> Result<Self, SpecError> {
+		let spec = try!(ethjson::spec::Spec::load(reader).map_err(|e| SpecError::InvalidJson(format!("{}", e))));
+		Spec::from_json(spec, registry)
+	}
+
+	/// Loads spec from json file using the default builtin registry.
+	/// Panics if the spec is invalid.
 	pub fn load(reader: &[u8]) -> Self {
-		From::from(ethjson::spec::Spec::load(reader).expect("invalid json file"))
+		Spec::load_with_registry(reader, &BuiltinRegistry::default()).unwrap_or_else(|e| panic!("{}", e))
 	}
 
 	/// Create a new Spec which conforms to the Frontier-era Morden chain except that it's a NullEngine consensus.
@@ -249,6 +273,11 @@ impl Spec {
 	pub fn new_null() -> Spec {
 		Spec::load(include_bytes!("../../res/null.json"))
 	}
+
+	/// Create a new Spec with `InstantSeal` consensus which prefunds addresses whose secrets are sha3(''), sha3('1') and sha3('2').
+	pub fn new_instant() -> Spec {
+		Spec::load(include_bytes!("../../res/instant_seal.json"))
+	}
 }
 
 #[cfg(test)]
@@ -257,6 +286,8 @@ mod tests {
 	use util::hash::*;
 	use util::sha3::*;
 	use views::*;
+	use builtin::BuiltinRegistry;
+	use error::SpecError;
 	use super::*;
 
 	#[test]
@@ -267,4 +298,10 @@ mod tests {
 		let genesis = test_spec.genesis_block();
 		assert_eq!(BlockView::new(&genesis).header_view().sha3(), H256::from_str("0cd786a2425d16f152c658316c423e6ce1181e15c3295826d7c9904cba9ce303").unwrap());
 	}
+
+	#[test]
+	fn unknown_builtin() {
+		let res = Spec::load_with_registry(include_bytes!("../../res/null_morden.json"), &BuiltinRegistry::new());
+		assert_eq!(res.err().unwrap(), SpecError::UnknownBuiltin("ecrecover".to_owned()));
+	}
 }
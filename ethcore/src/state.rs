@@ -23,6 +23,8 @@ use trace::Trace;
 use pod_account::*;
 use pod_state::{self, PodState};
 use types::state_diff::StateDiff;
+use types::state_override::StateOverride;
+use types::account_proof::{AccountProof, StorageProof};
 
 /// Used to return information about an `State::apply` operation.
 pub struct ApplyOutcome {
@@ -50,7 +52,6 @@ const SEC_TRIE_DB_UNWRAP_STR: &'static str = "A state can only be created with v
 
 impl State {
 	/// Creates new state with empty state root
-	#[cfg(test)]
 	pub fn new(mut db: Box<JournalDB>, account_start_nonce: U256, trie_factory: TrieFactory) -> State {
 		let mut root = H256::new();
 		{
@@ -180,6 +181,95 @@ impl State {
 		self.get(address, false).as_ref().map_or(H256::new(), |a|a.storage_at(&AccountDB::new(self.db.as_hashdb(), address), key))
 	}
 
+	/// Get Merkle proofs of account `a` and its storage slots `keys` against the state root.
+	/// Only committed changes are taken into account.
+	pub fn prove_account(&self, a: &Address, keys: &[H256]) -> AccountProof {
+		let db = self.trie_factory.readonly(self.db.as_hashdb(), &self.root).expect(SEC_TRIE_DB_UNWRAP_STR);
+		let (account, account_proof) = db.get_with_proof(a);
+		let account = account.map(Account::from_rlp);
+		let account_db = AccountDB::new(self.db.as_hashdb(), a);
+		let storage_proof = keys.iter().map(|key| {
+			let (value, proof) = account.as_ref().map_or_else(|| (H256::new(), vec![]), |account| account.prove_storage(&account_db, key));
+			StorageProof {
+				key: key.clone(),
+				value: value,
+				proof: proof,
+			}
+		}).collect();
+
+		AccountProof {
+			address: a.clone(),
+			balance: account.as_ref().map_or_else(U256::zero, |account| *account.balance()),
+			nonce: account.as_ref().map_or(self.account_start_nonce, |account| *account.nonce()),
+			code_hash: account.as_ref().map_or(SHA3_EMPTY, |account| account.code_hash()),
+			storage_hash: account.as_ref().and_then(|account| account.storage_root().cloned()).unwrap_or(SHA3_NULL_RLP),
+			account_proof: account_proof,
+			storage_proof: storage_proof,
+		}
+	}
+
+	/// List up to `count` accounts in trie order, starting after `after` if given.
+	/// Only committed changes are taken into account.
+	/// Fails with `ListError::Unavailable` if the state trie doesn't keep key preimages (not a fat DB).
+	pub fn list_accounts(&self, after: Option<&Address>, count: usize) -> Result<Vec<Address>, ListError> {
+		if !self.trie_factory.is_fat() {
+			return Err(ListError::Unavailable);
+		}
+
+		let trie = FatDB::new(self.db.as_hashdb(), &self.root).expect(SEC_TRIE_DB_UNWRAP_STR);
+		list_keys(trie.iter(), after, count)
+	}
+
+	/// List up to `count` storage keys of account `a` in trie order, starting after `after` if given.
+	/// Only committed changes are taken into account.
+	/// Fails with `ListError::Unavailable` if the state trie doesn't keep key preimages (not a fat DB).
+	pub fn list_storage(&self, a: &Address, after: Option<&H256>, count: usize) -> Result<Vec<H256>, ListError> {
+		if !self.trie_factory.is_fat() {
+			return Err(ListError::Unavailable);
+		}
+
+		let db = self.trie_factory.readonly(self.db.as_hashdb(), &self.root).expect(SEC_TRIE_DB_UNWRAP_STR);
+		let storage_root = match db.get(a).map(Account::from_rlp) {
+			Some(account) => account.storage_root().cloned().expect("account loaded from the trie has clean storage; qed"),
+			None => return Ok(Vec::new()),
+		};
+		let account_db = AccountDB::new(self.db.as_hashdb(), a);
+		let trie = FatDB::new(&account_db, &storage_root).expect(SEC_TRIE_DB_UNWRAP_STR);
+		list_keys(trie.iter(), after, count)
+	}
+
+	/// Call `f` for every account in trie order, with its full code and storage.
+	/// Only committed changes are taken into account.
+	/// Fails with `ListError::Unavailable` without calling `f` if the state trie doesn't keep key preimages
+	/// (not a fat DB), and with `ListError::MissingPreimage` as soon as a key without preimage is found.
+	pub fn for_each_pod_account<F>(&self, mut f: F) -> Result<(), ListError> where F: FnMut(Address, PodAccount) {
+		if !self.trie_factory.is_fat() {
+			return Err(ListError::Unavailable);
+		}
+
+		let trie = FatDB::new(self.db.as_hashdb(), &self.root).expect(SEC_TRIE_DB_UNWRAP_STR);
+		for item in trie.iter() {
+			let (address, rlp) = try!(item);
+			let address = Address::from_slice(&address);
+			let mut account = Account::from_rlp(rlp);
+			let account_db = AccountDB::new(self.db.as_hashdb(), &address);
+			account.cache_code(&account_db);
+			let storage_root = account.storage_root().cloned().expect("account loaded from the trie has clean storage; qed");
+			let mut storage = BTreeMap::new();
+			for item in FatDB::new(&account_db, &storage_root).expect(SEC_TRIE_DB_UNWRAP_STR).iter() {
+				let (key, value) = try!(item);
+				storage.insert(H256::from_slice(&key), H256::from(decode::<U256>(value)));
+			}
+			f(address, PodAccount {
+				balance: *account.balance(),
+				nonce: *account.nonce(),
+				code: account.code().map_or_else(Vec::new, |c| c.to_vec()),
+				storage: storage,
+			});
+		}
+		Ok(())
+	}
+
 	/// Mutate storage of account `a` so that it is `value` for `key`.
 	pub fn code(&self, a: &Address) -> Option<Bytes> {
 		self.get(a, true).as_ref().map_or(None, |a|a.code().map(|x|x.to_vec()))
@@ -208,6 +298,11 @@ impl State {
 		self.require(a, false).inc_nonce()
 	}
 
+	/// Set the nonce of account `a` to `nonce`.
+	pub fn set_nonce(&mut self, a: &Address, nonce: U256) {
+		self.require(a, false).set_nonce(nonce)
+	}
+
 	/// Mutate storage of account `a` so that it is `value` for `key`.
 	pub fn set_storage(&mut self, a: &Address, key: H256, value: H256) {
 		self.require(a, false).set_storage(key, value)
@@ -224,6 +319,32 @@ impl State {
 		self.require_or_from(a, true, || Account::new_contract(0.into(), self.account_start_nonce), |_|{}).reset_code(code);
 	}	
 
+	/// Apply `overrides` to the accounts of this state; used to simulate calls on a modified state.
+	/// The changes are made to the cache only and should never be committed.
+	pub fn apply_overrides(&mut self, overrides: &StateOverride) {
+		for (address, account) in overrides {
+			if let Some(ref balance) = account.balance {
+				let current = self.balance(address);
+				if *balance > current {
+					self.add_balance(address, &(*balance - current));
+				} else {
+					self.sub_balance(address, &(current - *balance));
+				}
+			}
+			if let Some(nonce) = account.nonce {
+				self.set_nonce(address, nonce);
+			}
+			if let Some(ref code) = account.code {
+				self.reset_code(address, code.clone());
+			}
+			if let Some(ref storage) = account.storage {
+				for (key, value) in storage {
+					self.set_storage(address, key.clone(), value.clone());
+				}
+			}
+		}
+	}
+
 	/// Execute a given transaction.
 	/// This will change the state accordingly.
 	pub fn apply(&mut self, env_info: &EnvInfo, engine: &Engine, vm_factory: &EvmFactory, t: &SignedTransaction, tracing: bool) -> ApplyResult {
@@ -281,9 +402,8 @@ impl State {
 		self.cache.borrow_mut().clear();
 	}
 
-	#[cfg(test)]
-	#[cfg(feature = "json-tests")]
-	/// Populate the state from `accounts`.
+	/// Populate the state from `accounts`, overwriting any cached entries for the same addresses.
+	/// The accounts are only written to the trie by a subsequent `commit`.
 	pub fn populate_from(&mut self, accounts: PodState) {
 		assert!(self.snapshots.borrow().is_empty());
 		for (add, acc) in accounts.drain().into_iter() {
@@ -370,6 +490,24 @@ impl State {
 	}
 }
 
+/// Collect up to `count` keys yielded by `iter`, starting after `after` if given.
+fn list_keys<T>(mut iter: FatDBIterator, after: Option<&T>, count: usize) -> Result<Vec<T>, ListError> where T: FixedHash + Deref<Target = [u8]> + PartialEq {
+	if let Some(after) = after {
+		iter.seek(after);
+	}
+	let mut keys = Vec::new();
+	for item in iter {
+		if keys.len() == count {
+			break;
+		}
+		let key = T::from_slice(&try!(item).0);
+		if Some(&key) != after {
+			keys.push(key);
+		}
+	}
+	Ok(keys)
+}
+
 impl fmt::Debug for State {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		write!(f, "{:?}", self.cache.borrow())
@@ -403,6 +541,7 @@ use transaction::*;
 use util::log::init_log;
 use trace::trace;
 use trace::trace::{Trace};
+use error::ListError;
 
 #[test]
 fn should_apply_create_transaction() {
@@ -620,8 +759,115 @@ fn should_trace_call_transaction_to_builtin() {
 			gas: 79_000.into(),
 			input: vec![],
 		}),
-		result: trace::Res::Call(trace::CallResult {
+		result: trace::Res::BuiltinCall(trace::BuiltinCallResult {
+			name: "ecrecover".into(),
 			gas_used: U256::from(3000),
+			arg_sizes: vec![0],
+			output: vec![],
+			error: None,
+		}),
+		subs: vec![]
+	}));
+}
+
+#[test]
+fn should_trace_failed_call_transaction_to_failing_builtin() {
+	use null_engine::NullEngine;
+	use builtin::{Builtin, BuiltinError};
+	init_log();
+
+	let temp = RandomTempPath::new();
+	let mut state = get_temp_state_in(temp.as_path());
+
+	let mut info = EnvInfo::default();
+	info.gas_limit = 1_000_000.into();
+	let mut builtins = BTreeMap::new();
+	builtins.insert(0x1.into(), Builtin::new(
+		"failing",
+		Box::new(|_: &[u8]| U256::from(3000)),
+		Box::new(|_: &[u8], _: &mut [u8]| Err(BuiltinError("malformed input")))
+	));
+	let engine = NullEngine::new(Spec::new_test().params, builtins);
+
+	let t = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Call(0x1.into()),
+		value: 0.into(),
+		data: vec![],
+	}.sign(&"".sha3());
+
+	let vm_factory = Default::default();
+	let result = state.apply(&info, &engine, &vm_factory, &t, true).unwrap();
+
+	assert_eq!(result.receipt.gas_used, U256::from(100_000));
+	assert_eq!(result.trace, Some(Trace {
+		depth: 0,
+		action: trace::Action::Call(trace::Call {
+			from: "9cce34f7ab185c7aba1b7c8140d620b4bda941d6".into(),
+			to: "0000000000000000000000000000000000000001".into(),
+			value: 0.into(),
+			gas: 79_000.into(),
+			input: vec![],
+		}),
+		result: trace::Res::BuiltinCall(trace::BuiltinCallResult {
+			name: "failing".into(),
+			gas_used: U256::from(79_000),
+			arg_sizes: vec![0],
+			output: vec![],
+			error: Some("malformed input".into()),
+		}),
+		subs: vec![]
+	}));
+}
+
+#[test]
+fn should_treat_inactive_builtin_as_plain_account() {
+	use null_engine::NullEngine;
+	use builtin::{Builtin, BuiltinError};
+	init_log();
+
+	let temp = RandomTempPath::new();
+	let mut state = get_temp_state_in(temp.as_path());
+
+	let mut info = EnvInfo::default();
+	info.gas_limit = 1_000_000.into();
+	info.number = 9;
+	let mut builtin = Builtin::new(
+		"failing",
+		Box::new(|_: &[u8]| U256::from(3000)),
+		Box::new(|_: &[u8], _: &mut [u8]| Err(BuiltinError("malformed input")))
+	);
+	builtin.activate_at = 10;
+	let mut builtins = BTreeMap::new();
+	builtins.insert(0x1.into(), builtin);
+	let engine = NullEngine::new(Spec::new_test().params, builtins);
+
+	let t = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Call(0x1.into()),
+		value: 0.into(),
+		data: vec![],
+	}.sign(&"".sha3());
+
+	let vm_factory = Default::default();
+	let result = state.apply(&info, &engine, &vm_factory, &t, true).unwrap();
+
+	assert_eq!(result.receipt.gas_used, U256::from(21_000));
+	assert_eq!(result.trace, Some(Trace {
+		depth: 0,
+		action: trace::Action::Call(trace::Call {
+			from: "9cce34f7ab185c7aba1b7c8140d620b4bda941d6".into(),
+			to: "0000000000000000000000000000000000000001".into(),
+			value: 0.into(),
+			gas: 79_000.into(),
+			input: vec![],
+		}),
+		result: trace::Res::Call(trace::CallResult {
+			gas_used: U256::from(0),
 			output: vec![]
 		}),
 		subs: vec![]
@@ -629,7 +875,7 @@ fn should_trace_call_transaction_to_builtin() {
 }
 
 #[test]
-fn should_not_trace_subcall_transaction_to_builtin() {
+fn should_trace_subcall_transaction_to_builtin() {
 	init_log();
 
 	let temp = RandomTempPath::new();
@@ -665,7 +911,24 @@ fn should_not_trace_subcall_transaction_to_builtin() {
 			gas_used: U256::from(28_061),
 			output: vec![]
 		}),
-		subs: vec![]
+		subs: vec![Trace {
+			depth: 1,
+			action: trace::Action::Call(trace::Call {
+				from: 0xa.into(),
+				to: 0x1.into(),
+				value: 0.into(),
+				gas: 3040.into(),
+				input: vec![],
+			}),
+			result: trace::Res::BuiltinCall(trace::BuiltinCallResult {
+				name: "ecrecover".into(),
+				gas_used: U256::from(3000),
+				arg_sizes: vec![0],
+				output: vec![],
+				error: None,
+			}),
+			subs: vec![]
+		}]
 	});
 	assert_eq!(result.trace, expected_trace);
 }
@@ -1287,6 +1550,133 @@ fn alter_nonce() {
 	assert_eq!(state.nonce(&a), U256::from(3u64));
 }
 
+#[test]
+fn apply_overrides() {
+	use types::state_override::AccountOverride;
+
+	let mut state_result = get_temp_state();
+	let mut state = state_result.reference_mut();
+	let a = Address::zero();
+	let b = address_from_u64(1u64);
+	state.add_balance(&a, &U256::from(69u64));
+	state.set_storage(&a, H256::from(1), H256::from(2));
+	state.commit();
+
+	let mut storage = BTreeMap::new();
+	storage.insert(H256::from(3), H256::from(4));
+	let mut overrides = BTreeMap::new();
+	overrides.insert(a.clone(), AccountOverride {
+		balance: Some(U256::from(42u64)),
+		nonce: Some(U256::from(7u64)),
+		code: None,
+		storage: Some(storage),
+	});
+	overrides.insert(b.clone(), AccountOverride {
+		balance: Some(U256::from(100u64)),
+		code: Some(vec![0x60, 0x00]),
+		..Default::default()
+	});
+	state.apply_overrides(&overrides);
+
+	assert_eq!(state.balance(&a), U256::from(42u64));
+	assert_eq!(state.nonce(&a), U256::from(7u64));
+	assert_eq!(state.storage_at(&a, &H256::from(1)), H256::from(2));
+	assert_eq!(state.storage_at(&a, &H256::from(3)), H256::from(4));
+	assert_eq!(state.balance(&b), U256::from(100u64));
+	assert_eq!(state.code(&b), Some(vec![0x60, 0x00]));
+}
+
+#[test]
+fn prove_account() {
+	let mut state_result = get_temp_state();
+	let mut state = state_result.reference_mut();
+	let a = Address::zero();
+	let b = address_from_u64(1u64);
+	state.add_balance(&a, &U256::from(69u64));
+	state.set_storage(&a, H256::from(1), H256::from(2));
+	state.add_balance(&b, &U256::from(1u64));
+	state.commit();
+	let root = state.root().clone();
+
+	let proof = state.prove_account(&a, &[H256::from(1), H256::from(3)]);
+	assert_eq!(proof.balance, U256::from(69u64));
+	assert_eq!(proof.storage_proof[0].value, H256::from(2));
+	assert_eq!(proof.storage_proof[1].value, H256::new());
+	assert!(proof.verify(&root));
+
+	let mut forged = proof.clone();
+	forged.storage_proof[0].value = H256::from(3);
+	assert!(!forged.verify(&root));
+	forged = proof.clone();
+	forged.balance = U256::from(70u64);
+	assert!(!forged.verify(&root));
+
+	let missing = state.prove_account(&address_from_u64(2u64), &[H256::from(1)]);
+	assert_eq!(missing.balance, U256::zero());
+	assert!(missing.verify(&root));
+}
+
+#[test]
+fn list_accounts_and_storage() {
+	let temp = RandomTempPath::new();
+	let db = journaldb::new(temp.as_str(), journaldb::Algorithm::Archive, DatabaseConfig::default());
+	let mut state = State::new(db, U256::from(0), TrieFactory::new(TrieSpec::Fat));
+	let a = Address::zero();
+	let b = address_from_u64(1u64);
+	state.add_balance(&a, &U256::from(69u64));
+	state.set_storage(&a, H256::from(1), H256::from(2));
+	state.set_storage(&a, H256::from(3), H256::from(4));
+	state.add_balance(&b, &U256::from(1u64));
+	state.commit();
+
+	let accounts = state.list_accounts(None, 10).unwrap();
+	assert_eq!(accounts.len(), 2);
+	assert!(accounts.contains(&a) && accounts.contains(&b));
+	assert_eq!(state.list_accounts(None, 1).unwrap(), vec![accounts[0].clone()]);
+	assert_eq!(state.list_accounts(Some(&accounts[0]), 10).unwrap(), vec![accounts[1].clone()]);
+	// listing after an unknown address continues from where it would be in trie order
+	let c = address_from_u64(2u64);
+	let after_c: Vec<_> = accounts.iter().filter(|a| a.sha3() > c.sha3()).cloned().collect();
+	assert_eq!(state.list_accounts(Some(&c), 10).unwrap(), after_c);
+
+	let keys = state.list_storage(&a, None, 10).unwrap();
+	assert_eq!(keys.len(), 2);
+	assert!(keys.contains(&H256::from(1)) && keys.contains(&H256::from(3)));
+	assert_eq!(state.list_storage(&a, Some(&keys[0]), 10).unwrap(), vec![keys[1].clone()]);
+	assert_eq!(state.list_storage(&b, None, 10).unwrap(), vec![]);
+
+	let mut pod = BTreeMap::new();
+	state.for_each_pod_account(|address, account| { pod.insert(address, account); }).unwrap();
+	assert_eq!(pod.len(), 2);
+	assert_eq!(pod[&a].balance, U256::from(69u64));
+	assert_eq!(pod[&a].storage, map![H256::from(1) => H256::from(2), H256::from(3) => H256::from(4)]);
+	assert!(pod[&b].storage.is_empty());
+
+	let mut state_result = get_temp_state();
+	let state = state_result.reference_mut();
+	assert_eq!(state.list_accounts(None, 10), Err(ListError::Unavailable));
+	assert_eq!(state.list_storage(&a, None, 10), Err(ListError::Unavailable));
+	assert_eq!(state.for_each_pod_account(|_, _| panic!("not a fat DB")), Err(ListError::Unavailable));
+}
+
+#[test]
+fn list_accounts_without_preimages() {
+	let temp = RandomTempPath::new();
+	let mut state = {
+		// a secure trie has the same layout as a fat one, just without the key preimages
+		let db = journaldb::new(temp.as_str(), journaldb::Algorithm::Archive, DatabaseConfig::default());
+		let mut state = State::new(db, U256::from(0), TrieFactory::new(TrieSpec::Secure));
+		state.add_balance(&Address::zero(), &U256::from(69u64));
+		state.commit();
+		state
+	};
+	state.trie_factory = TrieFactory::new(TrieSpec::Fat);
+
+	let missing = Address::zero().sha3();
+	assert_eq!(state.list_accounts(None, 10), Err(ListError::MissingPreimage(missing)));
+	assert_eq!(state.for_each_pod_account(|_, _| panic!("no preimage")), Err(ListError::MissingPreimage(missing)));
+}
+
 #[test]
 fn balance_nonce() {
 	let mut state_result = get_temp_state();
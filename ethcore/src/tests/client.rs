@@ -14,12 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-use client::{BlockChainClient, MiningBlockChainClient, Client, ClientConfig, BlockID};
+use client::{BlockChainClient, MiningBlockChainClient, Client, ClientConfig, BlockID, TransactionID, CallAnalytics};
 use block::IsBlock;
 use tests::helpers::*;
 use common::*;
 use devtools::*;
-use miner::Miner;
+use miner::{Miner, MinerService};
+use spec::Spec;
 
 #[test]
 fn imports_from_empty() {
@@ -112,6 +113,23 @@ fn can_collect_garbage() {
 	assert!(client.blockchain_cache_info().blocks < 100 * 1024);
 }
 
+#[test]
+fn can_replay_transactions() {
+	let client_result = generate_dummy_client_with_data(2, 2, &vec_into![0]);
+	let client = client_result.reference();
+	let analytics = CallAnalytics { transaction_tracing: true, vm_tracing: false, state_diffing: true };
+
+	let block = client.replay_block_transactions(BlockID::Number(2), analytics).unwrap();
+	assert_eq!(block.len(), 2);
+	assert!(block.iter().all(|e| e.trace.is_some() && e.state_diff.is_some()));
+
+	let second = client.replay(TransactionID::Location(BlockID::Number(2), 1), analytics).unwrap();
+	assert_eq!(second.gas_used, block[1].gas_used);
+	assert_eq!(second.contracts_created, block[1].contracts_created);
+	assert!(client.replay(TransactionID::Location(BlockID::Number(2), 2), analytics).is_err());
+	assert_eq!(client.replay_block_transactions(BlockID::Number(3), analytics).unwrap_err(), CallError::BlockNotFound);
+}
+
 #[test]
 #[cfg_attr(feature="dev", allow(useless_vec))]
 fn can_generate_gas_price_statistics() {
@@ -154,3 +172,27 @@ fn can_mine() {
 
 	assert_eq!(*b.block().header().parent_hash(), BlockView::new(&dummy_blocks[0]).header_view().sha3());
 }
+
+#[test]
+fn instant_seal_mines_own_transaction() {
+	let dir = RandomTempPath::new();
+	let miner = Arc::new(Miner::with_spec(Spec::new_instant()));
+	let client = Client::new(ClientConfig::default(), Spec::new_instant(), dir.as_path(), miner.clone(), IoChannel::disconnected()).unwrap();
+	let keypair = KeyPair::from_secret("".sha3()).unwrap();
+	let transaction = Transaction {
+		action: Action::Create,
+		value: U256::zero(),
+		data: vec![],
+		gas: U256::from(100_000),
+		gas_price: U256::from(20_000_000_000u64),
+		nonce: U256::zero(),
+	}.sign(keypair.secret());
+
+	miner.import_own_transaction(&*client, transaction).unwrap();
+	client.flush_queue();
+	client.import_verified_blocks();
+
+	assert_eq!(client.chain_info().best_block_number, 1);
+	let block = client.block(BlockID::Number(1)).unwrap();
+	assert_eq!(BlockView::new(&block).transactions_count(), 1);
+}
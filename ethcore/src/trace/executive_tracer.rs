@@ -18,7 +18,7 @@
 
 use util::{Bytes, Address, U256};
 use action_params::ActionParams;
-use trace::trace::{Trace, Call, Create, Action, Res, CreateResult, CallResult, VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff};
+use trace::trace::{Trace, Call, Create, Action, Res, CreateResult, CallResult, BuiltinCallResult, VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff};
 use trace::{Tracer, VMTracer};
 
 /// Simple executive tracer. Traces all calls and creates. Ignores delegatecalls.
@@ -97,6 +97,21 @@ impl Tracer for ExecutiveTracer {
 		self.traces.push(trace);
 	}
 
+	fn trace_builtin_call(&mut self, call: Option<Call>, result: Option<BuiltinCallResult>, depth: usize, delegate_call: bool) {
+		// don't trace if it's DELEGATECALL or CALLCODE.
+		if delegate_call {
+			return;
+		}
+
+		let trace = Trace {
+			depth: depth,
+			subs: vec![],
+			action: Action::Call(call.expect("self.prepare_trace_call().is_some(): so we must be tracing: qed")),
+			result: Res::BuiltinCall(result.expect("self.prepare_trace_call().is_some(): so we must be tracing: qed")),
+		};
+		self.traces.push(trace);
+	}
+
 	fn subtracer(&self) -> Self {
 		ExecutiveTracer::default()
 	}
@@ -113,6 +128,8 @@ pub struct ExecutiveVMTracer {
 }
 
 impl VMTracer for ExecutiveVMTracer {
+	type Output = VMTrace;
+
 	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: &U256) -> bool {
 		self.data.operations.push(VMOperation {
 			pc: pc,
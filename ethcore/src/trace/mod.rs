@@ -25,6 +25,7 @@ mod executive_tracer;
 pub mod flat;
 mod import;
 mod noop_tracer;
+mod struct_log_tracer;
 
 pub use types::trace_types::*;
 pub use self::block::BlockTraces;
@@ -34,11 +35,12 @@ pub use self::error::Error;
 pub use types::trace_types::trace::{Trace, VMTrace, VMOperation, VMExecutedOperation, MemoryDiff, StorageDiff};
 pub use self::noop_tracer::{NoopTracer, NoopVMTracer};
 pub use self::executive_tracer::{ExecutiveTracer, ExecutiveVMTracer};
+pub use self::struct_log_tracer::StructLogTracer;
 pub use types::trace_types::filter::{Filter, AddressesFilter};
 pub use self::import::ImportRequest;
 pub use self::localized::LocalizedTrace;
 use util::{Bytes, Address, U256, H256};
-use self::trace::{Call, Create};
+use self::trace::{Call, Create, BuiltinCallResult};
 use action_params::ActionParams;
 use header::BlockNumber;
 
@@ -81,6 +83,9 @@ pub trait Tracer: Send {
 	/// Stores failed create trace.
 	fn trace_failed_create(&mut self, create: Option<Create>, depth: usize, subs: Vec<Trace>);
 
+	/// Stores trace of a call to a builtin contract, successful or not.
+	fn trace_builtin_call(&mut self, call: Option<Call>, result: Option<BuiltinCallResult>, depth: usize, delegate_call: bool);
+
 	/// Spawn subtracer which will be used to trace deeper levels of execution.
 	fn subtracer(&self) -> Self where Self: Sized;
 
@@ -90,6 +95,9 @@ pub trait Tracer: Send {
 
 /// Used by executive to build VM traces.
 pub trait VMTracer: Send {
+	/// Result of tracing, returned by `drain`.
+	type Output;
+
 	/// Trace the preparation to execute a single instruction.
 	/// @returns true if `trace_executed` should be called.
 	fn trace_prepare_execute(&mut self, _pc: usize, _instruction: u8, _gas_cost: &U256) -> bool { false }
@@ -104,7 +112,7 @@ pub trait VMTracer: Send {
 	fn done_subtrace(&mut self, sub: Self) where Self: Sized;
 
 	/// Consumes self and returns the VM trace.
-	fn drain(self) -> Option<VMTrace>;
+	fn drain(self) -> Option<Self::Output>;
 }
 
 /// `DbExtras` provides an interface to query extra data which is not stored in tracesdb,
@@ -19,7 +19,7 @@
 use util::{Bytes, Address, U256};
 use action_params::ActionParams;
 use trace::{Tracer, VMTracer};
-use trace::trace::{Trace, Call, Create, VMTrace};
+use trace::trace::{Trace, Call, Create, BuiltinCallResult, VMTrace};
 
 /// Nonoperative tracer. Does not trace anything.
 pub struct NoopTracer;
@@ -55,6 +55,11 @@ impl Tracer for NoopTracer {
 		assert!(create.is_none(), "self.prepare_trace_create().is_none(): so we can't be tracing: qed");
 	}
 
+	fn trace_builtin_call(&mut self, call: Option<Call>, result: Option<BuiltinCallResult>, _: usize, _: bool) {
+		assert!(call.is_none(), "self.prepare_trace_call().is_none(): so we can't be tracing: qed");
+		assert!(result.is_none(), "self.prepare_trace_call().is_none(): so we can't be tracing: qed");
+	}
+
 	fn subtracer(&self) -> Self {
 		NoopTracer
 	}
@@ -68,6 +73,8 @@ impl Tracer for NoopTracer {
 pub struct NoopVMTracer;
 
 impl VMTracer for NoopVMTracer {
+	type Output = VMTrace;
+
 	/// Trace the preparation to execute a single instruction.
 	fn trace_prepare_execute(&mut self, _pc: usize, _instruction: u8, _gas_cost: &U256) -> bool { false }
 
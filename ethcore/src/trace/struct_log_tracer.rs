@@ -0,0 +1,172 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-style struct log VM tracer.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use util::U256;
+use evm;
+use trace::VMTracer;
+use trace::struct_log::{StructLog, StructLogOptions};
+
+/// VM tracer recording the stack, memory and storage before every executed instruction.
+/// The state is rebuilt from the diffs reported by the VM, so each call frame starts empty.
+pub struct StructLogTracer {
+	options: StructLogOptions,
+	depth: usize,
+	stack: Vec<U256>,
+	memory: Vec<u8>,
+	storage: BTreeMap<U256, U256>,
+	/// Gas left after the last executed instruction of this frame.
+	gas_left: Option<U256>,
+	/// Index of the log of the instruction being executed and the number of stack items it takes.
+	executing: Option<(usize, usize)>,
+	logs: Vec<StructLog>,
+}
+
+impl StructLogTracer {
+	/// Creates new tracer with given capture options.
+	pub fn new(options: StructLogOptions) -> Self {
+		StructLogTracer {
+			options: options,
+			depth: 0,
+			stack: vec![],
+			memory: vec![],
+			storage: BTreeMap::new(),
+			gas_left: None,
+			executing: None,
+			logs: vec![],
+		}
+	}
+}
+
+impl VMTracer for StructLogTracer {
+	type Output = Vec<StructLog>;
+
+	fn trace_prepare_execute(&mut self, pc: usize, instruction: u8, gas_cost: &U256) -> bool {
+		let (name, args) = evm::instruction_info(instruction);
+		let op = match name {
+			"" => format!("opcode 0x{:x} not defined", instruction),
+			name => name.to_owned(),
+		};
+		self.logs.push(StructLog {
+			pc: pc,
+			op: op,
+			gas: self.gas_left.unwrap_or_else(U256::zero),
+			gas_cost: gas_cost.clone(),
+			depth: self.depth,
+			stack: if self.options.disable_stack { None } else { Some(self.stack.clone()) },
+			memory: if self.options.disable_memory { None } else { Some(self.memory.clone()) },
+			storage: self.storage.clone(),
+		});
+		self.executing = Some((self.logs.len() - 1, args));
+		true
+	}
+
+	fn trace_executed(&mut self, gas_used: U256, stack_push: &[U256], mem_diff: Option<(usize, &[u8])>, store_diff: Option<(U256, U256)>) {
+		let (index, args) = self.executing.take().expect("trace_executed is always called after a trace_prepare_execute");
+		if self.gas_left.is_none() {
+			// first instruction of the frame; it can't be a call so its cost is exact.
+			let log = &mut self.logs[index];
+			log.gas = gas_used + log.gas_cost;
+		}
+		self.gas_left = Some(gas_used);
+
+		if !self.options.disable_stack {
+			let len = self.stack.len();
+			self.stack.truncate(len - cmp::min(args, len));
+			self.stack.extend_from_slice(stack_push);
+		}
+		if let (false, Some((offset, data))) = (self.options.disable_memory, mem_diff) {
+			let end = offset + data.len();
+			if self.memory.len() < end {
+				// memory grows in words.
+				self.memory.resize((end + 31) / 32 * 32, 0);
+			}
+			self.memory[offset..end].copy_from_slice(data);
+		}
+		if let Some((key, value)) = store_diff {
+			self.storage.insert(key, value);
+		}
+	}
+
+	fn prepare_subtrace(&self, _code: &[u8]) -> Self {
+		StructLogTracer {
+			depth: self.depth + 1,
+			..StructLogTracer::new(self.options)
+		}
+	}
+
+	fn done_subtrace(&mut self, sub: Self) {
+		self.logs.extend(sub.logs);
+	}
+
+	fn drain(self) -> Option<Vec<StructLog>> { Some(self.logs) }
+}
+
+#[cfg(test)]
+mod tests {
+	use util::U256;
+	use trace::VMTracer;
+	use trace::struct_log::StructLogOptions;
+	use super::StructLogTracer;
+
+	#[test]
+	fn should_rebuild_stack_and_memory() {
+		let mut tracer = StructLogTracer::new(StructLogOptions::default()).prepare_subtrace(&[]);
+		// PUSH1 0x2a
+		assert!(tracer.trace_prepare_execute(0, 0x60, &3.into()));
+		tracer.trace_executed(97.into(), &[42.into()], None, None);
+		// PUSH1 0x00
+		tracer.trace_prepare_execute(2, 0x60, &3.into());
+		tracer.trace_executed(94.into(), &[0.into()], None, None);
+		// MSTORE
+		tracer.trace_prepare_execute(4, 0x52, &6.into());
+		let mut word = [0u8; 32];
+		word[31] = 42;
+		tracer.trace_executed(88.into(), &[], Some((0, &word)), None);
+		// STOP
+		tracer.trace_prepare_execute(5, 0x00, &0.into());
+
+		let logs = tracer.drain().unwrap();
+		assert_eq!(logs.len(), 4);
+		assert_eq!(logs[0].op, "PUSH1");
+		assert_eq!(logs[0].gas, U256::from(100));
+		assert_eq!(logs[0].depth, 1);
+		assert_eq!(logs[2].op, "MSTORE");
+		assert_eq!(logs[2].gas, U256::from(94));
+		assert_eq!(logs[2].stack, Some(vec![42.into(), 0.into()]));
+		assert_eq!(logs[2].memory, Some(vec![]));
+		assert_eq!(logs[3].stack, Some(vec![]));
+		assert_eq!(logs[3].memory, Some(word.to_vec()));
+	}
+
+	#[test]
+	fn should_not_capture_disabled_parts() {
+		let options = StructLogOptions { disable_memory: true, disable_stack: true };
+		let mut tracer = StructLogTracer::new(options).prepare_subtrace(&[]);
+		tracer.trace_prepare_execute(0, 0x60, &3.into());
+		tracer.trace_executed(97.into(), &[42.into()], None, Some((1.into(), 2.into())));
+		tracer.trace_prepare_execute(2, 0xfe, &0.into());
+
+		let logs = tracer.drain().unwrap();
+		assert_eq!(logs[1].stack, None);
+		assert_eq!(logs[1].memory, None);
+		assert_eq!(logs[1].op, "opcode 0xfe not defined");
+		assert_eq!(logs[1].storage.get(&1.into()), Some(&2.into()));
+	}
+}
@@ -0,0 +1,130 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Merkle proofs of accounts and their storage.
+
+use util::*;
+use ipc::binary::BinaryConvertError;
+use std::mem;
+use std::collections::VecDeque;
+
+/// Merkle proof of a single storage slot.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct StorageProof {
+	/// Storage key.
+	pub key: H256,
+	/// Value at the key.
+	pub value: H256,
+	/// Trie nodes from the storage root to the value, RLP-encoded.
+	pub proof: Vec<Bytes>,
+}
+
+/// Merkle proof of an account and some of its storage slots.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub struct AccountProof {
+	/// Address of the account.
+	pub address: Address,
+	/// Balance of the account.
+	pub balance: U256,
+	/// Nonce of the account.
+	pub nonce: U256,
+	/// Hash of the account's code.
+	pub code_hash: H256,
+	/// Root of the account's storage trie.
+	pub storage_hash: H256,
+	/// Trie nodes from the state root to the account, RLP-encoded.
+	pub account_proof: Vec<Bytes>,
+	/// Proofs of the requested storage slots.
+	pub storage_proof: Vec<StorageProof>,
+}
+
+impl AccountProof {
+	/// Checks all proofs against `state_root`, assuming secure (key-hashing) tries.
+	/// Nonces of non-existent accounts are not checked.
+	pub fn verify(&self, state_root: &H256) -> bool {
+		let account = match verify_proof(state_root, &self.address.sha3(), &self.account_proof) {
+			Ok(account) => account,
+			Err(_) => return false,
+		};
+		let account_valid = match account {
+			Some(rlp) => {
+				let mut stream = RlpStream::new_list(4);
+				stream.append(&self.nonce);
+				stream.append(&self.balance);
+				stream.append(&self.storage_hash);
+				stream.append(&self.code_hash);
+				stream.out() == rlp
+			},
+			None => self.balance.is_zero() && self.storage_hash == SHA3_NULL_RLP && self.code_hash == SHA3_EMPTY,
+		};
+
+		account_valid && self.storage_proof.iter().all(|s| {
+			if self.storage_hash == SHA3_NULL_RLP {
+				return s.value.is_zero();
+			}
+			match verify_proof(&self.storage_hash, &s.key.sha3(), &s.proof) {
+				Ok(None) => s.value.is_zero(),
+				Ok(Some(v)) => match UntrustedRlp::new(&v).as_val::<U256>() {
+					Ok(value) => H256::from(value) == s.value,
+					Err(_) => false,
+				},
+				Err(_) => false,
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use util::*;
+	use super::{AccountProof, StorageProof};
+
+	#[test]
+	fn should_reject_malformed_storage_value() {
+		let address = Address::from(1);
+		let key = H256::from(1);
+		let mut db = MemoryDB::new();
+
+		// storage leaf whose value is not valid RLP
+		let mut storage_root = H256::new();
+		SecTrieDBMut::new(&mut db, &mut storage_root).insert(&key, &[0xff, 0xff]);
+		let storage_proof = SecTrieDB::new(&db, &storage_root).unwrap().get_with_proof(&key).1;
+
+		let mut account = RlpStream::new_list(4);
+		account.append(&U256::zero());
+		account.append(&U256::from(1));
+		account.append(&storage_root);
+		account.append(&SHA3_EMPTY);
+		let mut state_root = H256::new();
+		SecTrieDBMut::new(&mut db, &mut state_root).insert(&address, &account.out());
+		let account_proof = SecTrieDB::new(&db, &state_root).unwrap().get_with_proof(&address).1;
+
+		let proof = AccountProof {
+			address: address,
+			balance: U256::from(1),
+			nonce: U256::zero(),
+			code_hash: SHA3_EMPTY,
+			storage_hash: storage_root,
+			account_proof: account_proof,
+			storage_proof: vec![StorageProof {
+				key: key,
+				value: H256::new(),
+				proof: storage_proof,
+			}],
+		};
+		assert!(!proof.verify(&state_root));
+	}
+}
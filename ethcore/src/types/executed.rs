@@ -133,5 +133,42 @@ impl fmt::Display for ExecutionError {
 	}
 }
 
+/// Result of executing a non-persistent call at a given block.
+#[derive(PartialEq, Debug, Binary)]
+pub enum CallError {
+	/// The requested block is not known.
+	BlockNotFound,
+	/// The state at the requested block is not available, e.g. because it was pruned.
+	StatePruned,
+	/// The state at the requested block exists but could not be opened.
+	StateCorrupt,
+	/// The transaction to replay is not known.
+	TransactionNotFound,
+	/// The transaction could not be executed.
+	Execution(ExecutionError),
+}
+
+impl From<ExecutionError> for CallError {
+	fn from(error: ExecutionError) -> Self {
+		CallError::Execution(error)
+	}
+}
+
+impl fmt::Display for CallError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use self::CallError::*;
+
+		let msg = match *self {
+			BlockNotFound => "Block not found.".into(),
+			StatePruned => "State at the requested block is not available; it may have been pruned.".into(),
+			StateCorrupt => "State at the requested block could not be opened.".into(),
+			TransactionNotFound => "Transaction not found.".into(),
+			Execution(ref e) => format!("{}", e),
+		};
+
+		f.write_fmt(format_args!("Call error ({}).", msg))
+	}
+}
+
 /// Transaction execution result.
 pub type ExecutionResult = Result<Executed, ExecutionError>;
@@ -0,0 +1,52 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Errors listing the keys of a fat DB.
+
+use std::fmt;
+use util::hash::H256;
+use util::trie::TrieError;
+use ipc::binary::BinaryConvertError;
+use std::mem;
+use std::collections::VecDeque;
+
+/// Error listing accounts or storage keys of a state.
+#[derive(Debug, PartialEq, Clone, Binary)]
+pub enum ListError {
+	/// The state is not available, or its DB doesn't keep key preimages (`--fat-db`).
+	Unavailable,
+	/// The DB doesn't contain the preimage of a key hash, e.g. because it was created
+	/// by a version which didn't record preimages.
+	MissingPreimage(H256),
+}
+
+impl From<TrieError> for ListError {
+	fn from(err: TrieError) -> Self {
+		match err {
+			TrieError::InvalidStateRoot => ListError::Unavailable,
+			TrieError::MissingPreimage(hash) => ListError::MissingPreimage(hash),
+		}
+	}
+}
+
+impl fmt::Display for ListError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ListError::Unavailable => write!(f, "State is not available or does not keep key preimages."),
+			ListError::MissingPreimage(ref hash) => write!(f, "Missing preimage of key hash {}.", hash),
+		}
+	}
+}
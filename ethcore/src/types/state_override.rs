@@ -0,0 +1,39 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! State overrides for non-persistent calls.
+
+use std::mem;
+use std::collections::{BTreeMap, VecDeque};
+use util::{Address, H256, U256, Bytes};
+use ipc::binary::BinaryConvertError;
+
+/// Changes to a single account applied before a call is executed.
+/// Fields left as `None` keep their value from the block's state.
+#[derive(Debug, PartialEq, Clone, Default, Binary)]
+pub struct AccountOverride {
+	/// Balance to use instead of the account's balance.
+	pub balance: Option<U256>,
+	/// Nonce to use instead of the account's nonce.
+	pub nonce: Option<U256>,
+	/// Code to use instead of the account's code.
+	pub code: Option<Bytes>,
+	/// Storage slots to set; slots not given keep their value.
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// Per-address overrides of the state a call is executed on.
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
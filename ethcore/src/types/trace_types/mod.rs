@@ -19,3 +19,4 @@
 pub mod filter;
 pub mod trace;
 pub mod localized;
+pub mod struct_log;
@@ -0,0 +1,63 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-style struct log traces.
+
+use util::{U256, Bytes};
+use ipc::binary::BinaryConvertError;
+use std::mem;
+use std::collections::{BTreeMap, VecDeque};
+
+/// Options concerning what is captured in struct logs.
+#[derive(Eq, PartialEq, Default, Clone, Copy, Debug, Binary)]
+pub struct StructLogOptions {
+	/// Don't capture the memory.
+	pub disable_memory: bool,
+	/// Don't capture the stack.
+	pub disable_stack: bool,
+}
+
+/// State of the VM before executing a single instruction.
+#[derive(Debug, Clone, PartialEq, Binary)]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: usize,
+	/// Instruction mnemonic.
+	pub op: String,
+	/// Gas available before executing the instruction.
+	pub gas: U256,
+	/// Gas cost of the instruction.
+	pub gas_cost: U256,
+	/// Call depth, starting at 1.
+	pub depth: usize,
+	/// Stack items, from bottom to top. `None` if stack capture is disabled.
+	pub stack: Option<Vec<U256>>,
+	/// Memory contents. `None` if memory capture is disabled.
+	pub memory: Option<Bytes>,
+	/// Storage slots of the current account written so far.
+	pub storage: BTreeMap<U256, U256>,
+}
+
+/// Struct logs of a replayed transaction.
+#[derive(Debug, Clone, PartialEq, Binary)]
+pub struct StructLogTrace {
+	/// Gas used by the transaction.
+	pub gas_used: U256,
+	/// Transaction output.
+	pub return_value: Bytes,
+	/// Logs of all executed instructions, in order of execution.
+	pub struct_logs: Vec<StructLog>,
+}
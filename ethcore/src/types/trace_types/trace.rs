@@ -87,6 +87,49 @@ impl Decodable for CreateResult {
 	}
 }
 
+/// Result of a call to a builtin contract.
+///
+/// Its RLP starts with the fields of `CallResult`, so that it can be read as one.
+#[derive(Debug, Clone, PartialEq, Default, Binary)]
+pub struct BuiltinCallResult {
+	/// Name of the builtin, as given in the chain spec.
+	pub name: String,
+	/// Gas charged for the call.
+	pub gas_used: U256,
+	/// Sizes of the arguments decoded from the call input.
+	pub arg_sizes: Vec<usize>,
+	/// Call output.
+	pub output: Bytes,
+	/// Reason the builtin failed, if it did.
+	pub error: Option<String>,
+}
+
+impl Encodable for BuiltinCallResult {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(5);
+		s.append(&self.gas_used);
+		s.append(&self.output);
+		s.append(&self.name);
+		s.append(&self.arg_sizes);
+		s.append(&self.error);
+	}
+}
+
+impl Decodable for BuiltinCallResult {
+	fn decode<D>(decoder: &D) -> Result<Self, DecoderError> where D: Decoder {
+		let d = decoder.as_rlp();
+		let res = BuiltinCallResult {
+			gas_used: try!(d.val_at(0)),
+			output: try!(d.val_at(1)),
+			name: try!(d.val_at(2)),
+			arg_sizes: try!(d.val_at(3)),
+			error: try!(d.val_at(4)),
+		};
+
+		Ok(res)
+	}
+}
+
 /// Description of a _call_ action, either a `CALL` operation or a message transction.
 #[derive(Debug, Clone, PartialEq, Binary)]
 pub struct Call {
@@ -263,6 +306,8 @@ pub enum Res {
 	FailedCall,
 	/// Failed create.
 	FailedCreate,
+	/// Call to a builtin contract, successful or not.
+	BuiltinCall(BuiltinCallResult),
 }
 
 impl Encodable for Res {
@@ -285,6 +330,13 @@ impl Encodable for Res {
 			Res::FailedCreate => {
 				s.begin_list(1);
 				s.append(&3u8);
+			},
+			// stored as a call (or failed call) result with extra fields, so that the trace DB
+			// stays readable by versions which don't know about builtin results
+			Res::BuiltinCall(ref builtin) => {
+				s.begin_list(2);
+				s.append(&if builtin.error.is_none() { 0u8 } else { 2u8 });
+				s.append(builtin);
 			}
 		}
 	}
@@ -295,8 +347,10 @@ impl Decodable for Res {
 		let d = decoder.as_rlp();
 		let action_type: u8 = try!(d.val_at(0));
 		match action_type {
+			0 if try!(d.at(1)).item_count() > 2 => d.val_at(1).map(Res::BuiltinCall),
 			0 => d.val_at(1).map(Res::Call),
 			1 => d.val_at(1).map(Res::Create),
+			2 if d.item_count() > 1 => d.val_at(1).map(Res::BuiltinCall),
 			2 => Ok(Res::FailedCall),
 			3 => Ok(Res::FailedCreate),
 			_ => Err(DecoderError::Custom("Invalid result type.")),
@@ -516,9 +570,9 @@ impl Decodable for VMTrace {
 #[cfg(test)]
 mod tests {
 	use util::{Address, U256, FixedHash};
-	use util::rlp::{encode, decode};
+	use util::rlp::{encode, decode, UntrustedRlp, View};
 	use util::sha3::Hashable;
-	use trace::trace::{Call, CallResult, Create, Res, Action, Trace};
+	use trace::trace::{Call, CallResult, BuiltinCallResult, Create, Res, Action, Trace};
 
 	#[test]
 	fn traces_rlp() {
@@ -555,6 +609,72 @@ mod tests {
 		assert_eq!(trace, decoded);
 	}
 
+	#[test]
+	fn builtin_traces_rlp() {
+		let call = Action::Call(Call {
+			from: Address::from(1),
+			to: Address::from(5),
+			value: U256::zero(),
+			gas: U256::from(4),
+			input: vec![0x5]
+		});
+		let trace = Trace {
+			depth: 0,
+			action: call.clone(),
+			subs: vec![],
+			result: Res::BuiltinCall(BuiltinCallResult {
+				name: "zkSNARK".into(),
+				gas_used: U256::from(10),
+				arg_sizes: vec![32, 64, 0],
+				output: vec![0x1],
+				error: None,
+			})
+		};
+		let failed = Trace {
+			depth: 0,
+			action: call,
+			subs: vec![],
+			result: Res::BuiltinCall(BuiltinCallResult {
+				name: "zkSNARK".into(),
+				gas_used: U256::from(4),
+				arg_sizes: vec![],
+				output: vec![],
+				error: Some("invalid ABI encoding".into()),
+			})
+		};
+
+		let decoded: Trace = decode(&encode(&trace));
+		assert_eq!(trace, decoded);
+		let decoded: Trace = decode(&encode(&failed));
+		assert_eq!(failed, decoded);
+	}
+
+	#[test]
+	fn builtin_results_read_as_call_results() {
+		let result = Res::BuiltinCall(BuiltinCallResult {
+			name: "zkSNARK".into(),
+			gas_used: U256::from(10),
+			arg_sizes: vec![32, 64, 0],
+			output: vec![0x1],
+			error: None,
+		});
+		let failed = Res::BuiltinCall(BuiltinCallResult {
+			name: "zkSNARK".into(),
+			gas_used: U256::from(4),
+			arg_sizes: vec![],
+			output: vec![],
+			error: Some("invalid ABI encoding".into()),
+		});
+
+		// the way versions unaware of builtin results decode them
+		let encoded = encode(&result);
+		let rlp = UntrustedRlp::new(&encoded);
+		assert_eq!(rlp.val_at::<u8>(0).unwrap(), 0);
+		assert_eq!(rlp.val_at::<CallResult>(1).unwrap(), CallResult { gas_used: U256::from(10), output: vec![0x1] });
+		let encoded = encode(&failed);
+		assert_eq!(UntrustedRlp::new(&encoded).val_at::<u8>(0).unwrap(), 2);
+	}
+
 	#[test]
 	fn traces_bloom() {
 		let trace = Trace {
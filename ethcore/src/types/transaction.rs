@@ -76,7 +76,13 @@ pub struct Transaction {
 impl Transaction {
 	/// Append object with a without signature into RLP stream
 	pub fn rlp_append_unsigned_transaction(&self, s: &mut RlpStream) {
-		s.begin_list(6);
+		self.rlp_append_unsigned_transaction_with_chain_id(s, None)
+	}
+
+	/// Append object with a without signature into RLP stream.
+	/// With `chain_id` given, the EIP-155 fields `chain_id, 0, 0` are appended as well.
+	pub fn rlp_append_unsigned_transaction_with_chain_id(&self, s: &mut RlpStream, chain_id: Option<u64>) {
+		s.begin_list(if chain_id.is_none() { 6 } else { 9 });
 		s.append(&self.nonce);
 		s.append(&self.gas_price);
 		s.append(&self.gas);
@@ -86,6 +92,11 @@ impl Transaction {
 		};
 		s.append(&self.value);
 		s.append(&self.data);
+		if let Some(n) = chain_id {
+			s.append(&n);
+			s.append(&0u8);
+			s.append(&0u8);
+		}
 	}
 }
 
@@ -133,25 +144,41 @@ impl From<ethjson::transaction::Transaction> for SignedTransaction {
 impl Transaction {
 	/// The message hash of the transaction.
 	pub fn hash(&self) -> H256 {
+		self.hash_with_chain_id(None)
+	}
+
+	/// The message hash of the transaction.
+	/// With `chain_id` given, the hash commits to the chain as specified by EIP-155.
+	pub fn hash_with_chain_id(&self, chain_id: Option<u64>) -> H256 {
 		let mut stream = RlpStream::new();
-		self.rlp_append_unsigned_transaction(&mut stream);
+		self.rlp_append_unsigned_transaction_with_chain_id(&mut stream, chain_id);
 		stream.out().sha3()
 	}
 
 	/// Signs the transaction as coming from `sender`.
 	pub fn sign(self, secret: &Secret) -> SignedTransaction {
-		let sig = ec::sign(secret, &self.hash()).unwrap();
-		self.with_signature(sig)
+		self.sign_with_chain_id(secret, None)
+	}
+
+	/// Signs the transaction as coming from `sender`, replay-protected for `chain_id` if given.
+	pub fn sign_with_chain_id(self, secret: &Secret, chain_id: Option<u64>) -> SignedTransaction {
+		let sig = ec::sign(secret, &self.hash_with_chain_id(chain_id)).unwrap();
+		self.with_signature_with_chain_id(sig, chain_id)
 	}
 
 	/// Signs the transaction with signature.
 	pub fn with_signature(self, sig: H520) -> SignedTransaction {
+		self.with_signature_with_chain_id(sig, None)
+	}
+
+	/// Signs the transaction with signature of `self.hash_with_chain_id(chain_id)`.
+	pub fn with_signature_with_chain_id(self, sig: H520, chain_id: Option<u64>) -> SignedTransaction {
 		let (r, s, v) = sig.to_rsv();
 		SignedTransaction {
 			unsigned: self,
 			r: r,
 			s: s,
-			v: v + 27,
+			v: v as u64 + chain_id.map_or(27, |n| n * 2 + 35),
 			hash: Cell::new(None),
 			sender: Cell::new(None),
 		}
@@ -201,8 +228,9 @@ impl Transaction {
 pub struct SignedTransaction {
 	/// Plain Transaction.
 	unsigned: Transaction,
-	/// The V field of the signature, either 27 or 28; helps describe the point on the curve.
-	v: u8,
+	/// The V field of the signature; helps describe the point on the curve.
+	/// Either 27 or 28, or `chain_id * 2 + 35` or `+ 36` for replay-protected (EIP-155) transactions.
+	v: u64,
 	/// The R field of the signature; helps describe the point on the curve.
 	r: U256,
 	/// The S field of the signature; helps describe the point on the curve.
@@ -286,8 +314,11 @@ impl SignedTransaction {
 		}
 	}
 
-	/// 0 is `v` is 27, 1 if 28, and 4 otherwise.
-	pub fn standard_v(&self) -> u8 { match self.v { 27 => 0, 28 => 1, _ => 4 } }
+	/// 0 is `v` is 27 or `chain_id * 2 + 35`, 1 if 28 or `chain_id * 2 + 36`, and 4 otherwise.
+	pub fn standard_v(&self) -> u8 { match self.v { 27 => 0, 28 => 1, v if v >= 35 => ((v - 35) % 2) as u8, _ => 4 } }
+
+	/// The chain id this transaction is replay-protected for (EIP-155), if any.
+	pub fn chain_id(&self) -> Option<u64> { match self.v { v if v >= 35 => Some((v - 35) / 2), _ => None } }
 
 	/// Construct a signature object from the sig.
 	pub fn signature(&self) -> Signature { Signature::from_rsv(&From::from(&self.r), &From::from(&self.s), self.standard_v()) }
@@ -301,13 +332,22 @@ impl SignedTransaction {
 		}
 	}
 
+	/// Checks whether the transaction may be included on a chain which accepts only `chain_id`
+	/// (or nothing if `None`) for replay-protected transactions. Unprotected ones are always fine.
+	pub fn check_chain_id(&self, chain_id: Option<u64>) -> Result<(), Error> {
+		match self.chain_id() {
+			Some(n) if Some(n) != chain_id => Err(From::from(TransactionError::InvalidChainId { expected: chain_id, got: n })),
+			_ => Ok(()),
+		}
+	}
+
 	/// Returns transaction sender.
 	pub fn sender(&self) -> Result<Address, Error> {
 		let sender = self.sender.get();
 		match sender {
 			Some(s) => Ok(s),
 			None => {
-				let s = Address::from(try!(ec::recover(&self.signature(), &self.unsigned.hash())).sha3());
+				let s = Address::from(try!(ec::recover(&self.signature(), &self.unsigned.hash_with_chain_id(self.chain_id()))).sha3());
 				self.sender.set(Some(s));
 				Ok(s)
 			}
@@ -395,3 +435,54 @@ fn fake_signing() {
 	let t = t.clone();
 	assert_eq!(Address::from(0x69), t.sender().unwrap());
 }
+
+#[test]
+fn should_recover_from_chain_specific_signing() {
+	let key = ::util::crypto::KeyPair::create().unwrap();
+	let t = Transaction {
+		action: Action::Create,
+		nonce: U256::from(42),
+		gas_price: U256::from(3000),
+		gas: U256::from(50_000),
+		value: U256::from(1),
+		data: b"Hello!".to_vec()
+	}.sign_with_chain_id(&key.secret(), Some(69));
+	assert_eq!(Address::from(key.public().sha3()), t.sender().unwrap());
+	assert_eq!(t.chain_id(), Some(69));
+	assert!(t.check_chain_id(Some(69)).is_ok());
+	assert!(t.check_chain_id(Some(1)).is_err());
+	assert!(t.check_chain_id(None).is_err());
+
+	let t: SignedTransaction = decode(&encode(&t));
+	assert_eq!(Address::from(key.public().sha3()), t.sender().unwrap());
+}
+
+#[test]
+fn should_recover_from_chain_zero_signing() {
+	let key = ::util::crypto::KeyPair::create().unwrap();
+	let t = Transaction::default().sign_with_chain_id(&key.secret(), Some(0));
+	assert_eq!(t.chain_id(), Some(0));
+	assert_eq!(Address::from(key.public().sha3()), t.sender().unwrap());
+	assert!(t.check_chain_id(Some(0)).is_ok());
+	assert!(t.check_chain_id(None).is_err());
+
+	let t: SignedTransaction = decode(&encode(&t));
+	assert_eq!(t.chain_id(), Some(0));
+	assert_eq!(Address::from(key.public().sha3()), t.sender().unwrap());
+}
+
+#[test]
+fn should_accept_unprotected_on_any_chain() {
+	let key = ::util::crypto::KeyPair::create().unwrap();
+	let t = Transaction::default().sign(&key.secret());
+	assert_eq!(t.chain_id(), None);
+	assert!(t.check_chain_id(Some(69)).is_ok());
+	assert!(t.check_chain_id(None).is_ok());
+}
+
+#[test]
+fn should_recover_eip155_example() {
+	let t: SignedTransaction = decode(&::rustc_serialize::hex::FromHex::from_hex("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap());
+	assert_eq!(t.chain_id(), Some(1));
+	assert_eq!(t.sender().unwrap(), address_from_hex("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"));
+}
@@ -70,6 +70,7 @@ pub fn verify_block_unordered(header: Header, bytes: Bytes, engine: &Engine) ->
 			transactions.push(t);
 		}
 	}
+	engine.preverify_builtin_calls(&header, &transactions);
 	Ok(PreverifiedBlock {
 		header: header,
 		transactions: transactions,
@@ -21,17 +21,27 @@
 extern crate ethcore;
 extern crate rustc_serialize;
 extern crate docopt;
+extern crate ethjson;
+extern crate serde_json;
 #[macro_use]
 extern crate ethcore_util as util;
 
 mod ext;
 
+use std::fs::File;
+use std::io::Read;
 use std::time::{Instant, Duration};
 use std::str::FromStr;
 use docopt::Docopt;
-use util::{U256, FromHex, Uint, Bytes};
+use rustc_serialize::hex::ToHex;
+use util::{U256, Address, FromHex, Uint, Bytes, clean_0x};
 use ethcore::evm::{Factory, VMType, Finalize};
 use ethcore::action_params::ActionParams;
+use ethcore::client::{EvmTestClient, EnvInfo};
+use ethcore::spec::Spec;
+use ethcore::builtin::BuiltinRegistry;
+use ethcore::pod_state::PodState;
+use ethcore::transaction::{Transaction, Action};
 
 const USAGE: &'static str = r#"
 EVM implementation for Parity.
@@ -39,6 +49,7 @@ EVM implementation for Parity.
 
 Usage:
     evmbin stats [options]
+    evmbin run --chain FILE --state FILE [options]
     evmbin [-h | --help]
 
 Transaction options:
@@ -46,6 +57,17 @@ Transaction options:
     --input DATA       Input data.
     --gas GAS          Supplied gas.
 
+Run options:
+    --chain FILE       Chain spec providing builtins and the schedule.
+    --state FILE       Pre-state of accounts, as JSON mapping addresses
+                       to balance, nonce, code and storage.
+    --from ADDRESS     Sender of the transaction [default: 0000000000000000000000000000000000000000].
+    --to ADDRESS       Recipient of the transaction. If not given, a contract
+                       is created with --input as its init code.
+    --value VALUE      Value transferred [default: 0].
+    --gas-price PRICE  Gas price [default: 0].
+    --number NUMBER    Number of the block the transaction is run in [default: 0].
+
 General options:
     -h, --help         Display this message and exit.
 "#;
@@ -54,6 +76,10 @@ General options:
 fn main() {
 	let args: Args = Docopt::new(USAGE).and_then(|d| d.decode()).unwrap_or_else(|e| e.exit());
 
+	if args.cmd_run {
+		return run_transaction(&args);
+	}
+
 	let mut params = ActionParams::default();
 	params.gas = args.gas();
 	params.code = Some(args.code());
@@ -62,7 +88,7 @@ fn main() {
 	let result = run_vm(params);
 	println!("Gas used: {:?}", result.gas_used);
 	println!("Output: {:?}", result.output);
-	println!("Time: {}.{:.9}s", result.time.as_secs(), result.time.subsec_nanos());
+	println!("Time: {}.{:09}s", result.time.as_secs(), result.time.subsec_nanos());
 }
 
 /// Execute VM with given `ActionParams`
@@ -83,6 +109,45 @@ pub fn run_vm(params: ActionParams) -> ExecutionResults {
 	}
 }
 
+/// Execute a full transaction against the state and chain given in `args` and print the results.
+fn run_transaction(args: &Args) {
+	let spec = args.spec();
+	let pre = args.pre_state();
+	let gas = args.flag_gas.as_ref().map_or(spec.gas_limit, |_| args.gas());
+	let transaction = Transaction {
+		nonce: pre.get().get(&args.from()).map_or_else(|| spec.engine.account_start_nonce(), |a| a.nonce),
+		gas_price: args.gas_price(),
+		gas: gas,
+		action: args.to().map_or(Action::Create, Action::Call),
+		value: args.value(),
+		data: args.data().unwrap_or_else(Vec::new),
+	}.fake_sign(args.from());
+
+	let mut env_info = EnvInfo::default();
+	env_info.number = args.number();
+	env_info.gas_limit = gas;
+
+	let client = EvmTestClient::new(spec, VMType::Interpreter);
+	let start = Instant::now();
+	let result = client.transact(&env_info, pre, &transaction, false);
+	let duration = start.elapsed();
+
+	match result {
+		Ok((executed, diff)) => {
+			println!("Output: {}", executed.output.to_hex());
+			println!("Gas used: {}", executed.gas_used);
+			println!("Logs:");
+			for log in &executed.logs {
+				println!("  {} topics: {:?} data: {}", log.address.hex(), log.topics, log.data.to_hex());
+			}
+			println!("State diff:");
+			print!("{}", diff);
+		},
+		Err(e) => println!("Execution failed: {}", e),
+	}
+	println!("Time: {}.{:09}s", duration.as_secs(), duration.subsec_nanos());
+}
+
 /// VM execution results
 pub struct ExecutionResults {
 	/// Used gas
@@ -96,16 +161,24 @@ pub struct ExecutionResults {
 #[derive(Debug, RustcDecodable)]
 struct Args {
 	cmd_stats: bool,
+	cmd_run: bool,
 	flag_code: Option<String>,
 	flag_gas: Option<String>,
 	flag_input: Option<String>,
+	flag_chain: Option<String>,
+	flag_state: Option<String>,
+	flag_from: String,
+	flag_to: Option<String>,
+	flag_value: String,
+	flag_gas_price: String,
+	flag_number: u64,
 }
 
 impl Args {
 	pub fn gas(&self) -> U256 {
 		self.flag_gas
 			.clone()
-			.and_then(|g| U256::from_str(&g).ok())
+			.and_then(|g| U256::from_dec_str(&g).ok())
 			.unwrap_or_else(|| !U256::zero())
 	}
 
@@ -121,6 +194,50 @@ impl Args {
 			.clone()
 			.and_then(|d| d.from_hex().ok())
 	}
+
+	pub fn spec(&self) -> Spec {
+		let path = self.flag_chain.as_ref().unwrap_or_else(|| die("Chain spec is required."));
+		let data = read_file(path).unwrap_or_else(|| die("Could not read chain spec."));
+		Spec::load_with_registry(&data, &BuiltinRegistry::default()).unwrap_or_else(|e| {
+			println!("Invalid chain spec: {}", e);
+			::std::process::exit(-1)
+		})
+	}
+
+	pub fn pre_state(&self) -> PodState {
+		let path = self.flag_state.as_ref().unwrap_or_else(|| die("Pre-state is required."));
+		let data = read_file(path).unwrap_or_else(|| die("Could not read pre-state."));
+		serde_json::from_slice::<ethjson::blockchain::State>(&data)
+			.map(PodState::from)
+			.unwrap_or_else(|_| die("Invalid pre-state."))
+	}
+
+	pub fn from(&self) -> Address {
+		Address::from_str(clean_0x(&self.flag_from)).unwrap_or_else(|_| die("Invalid sender address."))
+	}
+
+	pub fn to(&self) -> Option<Address> {
+		self.flag_to
+			.as_ref()
+			.map(|to| Address::from_str(clean_0x(to)).unwrap_or_else(|_| die("Invalid recipient address.")))
+	}
+
+	pub fn value(&self) -> U256 {
+		U256::from_dec_str(&self.flag_value).unwrap_or_else(|_| die("Invalid value."))
+	}
+
+	pub fn gas_price(&self) -> U256 {
+		U256::from_dec_str(&self.flag_gas_price).unwrap_or_else(|_| die("Invalid gas price."))
+	}
+
+	pub fn number(&self) -> u64 {
+		self.flag_number
+	}
+}
+
+fn read_file(path: &str) -> Option<Bytes> {
+	let mut data = Vec::new();
+	File::open(path).and_then(|mut f| f.read_to_end(&mut data)).ok().map(|_| data)
 }
 
 
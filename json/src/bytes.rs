@@ -18,8 +18,8 @@
 
 use std::str::FromStr;
 use std::ops::Deref;
-use rustc_serialize::hex::FromHex;
-use serde::{Deserialize, Deserializer, Error};
+use rustc_serialize::hex::{FromHex, ToHex};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, Error};
 use serde::de::Visitor;
 
 /// Lenient bytes json deserialization for test json files.
@@ -66,6 +66,13 @@ impl FromStr for Bytes {
 	}
 }
 
+impl Serialize for Bytes {
+	fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+		where S: Serializer {
+		serializer.serialize_str(&format!("0x{}", self.0.to_hex()))
+	}
+}
+
 impl Deserialize for Bytes {
 	fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
 		where D: Deserializer {
@@ -105,6 +112,12 @@ mod test {
 		]);
 	}
 
+	#[test]
+	fn bytes_serialization() {
+		let serialized = serde_json::to_string(&vec![Bytes(vec![]), Bytes(vec![0x12, 0x34])]).unwrap();
+		assert_eq!(serialized, r#"["0x","0x1234"]"#);
+	}
+
 	#[test]
 	fn bytes_into() {
 		let bytes = Bytes(vec![0xff, 0x11]);
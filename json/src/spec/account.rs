@@ -16,24 +16,31 @@
 
 //! Spec account deserialization.
 
+use std::collections::BTreeMap;
 use uint::Uint;
+use bytes::Bytes;
 use spec::builtin::Builtin;
 
 /// Spec account.
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Account {
 	/// Builtin contract.
+	#[serde(skip_serializing)]
 	pub builtin: Option<Builtin>,
 	/// Balance.
 	pub balance: Option<Uint>,
 	/// Nonce.
 	pub nonce: Option<Uint>,
+	/// Code.
+	pub code: Option<Bytes>,
+	/// Storage.
+	pub storage: Option<BTreeMap<Uint, Uint>>,
 }
 
 impl Account {
-	/// Returns true if account does not have nonce and balance.
+	/// Returns true if account does not have nonce, balance, code and storage.
 	pub fn is_empty(&self) -> bool {
-		self.balance.is_none() && self.nonce.is_none()
+		self.balance.is_none() && self.nonce.is_none() && self.code.is_none() && self.storage.is_none()
 	}
 }
 
@@ -51,4 +58,25 @@ mod tests {
 		let _deserialized: Account = serde_json::from_str(s).unwrap();
 		// TODO: validate all fields
 	}
+
+	#[test]
+	fn account_with_code_and_storage_deserialization() {
+		let s = r#"{
+			"balance": "0x10",
+			"nonce": "0x1",
+			"code": "0x600140600155",
+			"storage": { "0x01": "0x02" }
+		}"#;
+		let deserialized: Account = serde_json::from_str(s).unwrap();
+		assert!(!deserialized.is_empty());
+		assert_eq!(deserialized.code.unwrap().len(), 6);
+		assert_eq!(deserialized.storage.unwrap().len(), 1);
+	}
+
+	#[test]
+	fn account_serialization_roundtrip() {
+		let s = r#"{"balance":"0x10","nonce":"0x1","code":"0x600140600155","storage":{"0x1":"0x2"}}"#;
+		let deserialized: Account = serde_json::from_str(s).unwrap();
+		assert_eq!(serde_json::to_string(&deserialized).unwrap(), s);
+	}
 }
@@ -16,6 +16,8 @@
 
 //! Spec builtin deserialization.
 
+use uint::Uint;
+
 /// Linear pricing.
 #[derive(Debug, PartialEq, Deserialize, Clone)]
 pub struct Linear {
@@ -25,12 +27,60 @@ pub struct Linear {
 	pub word: usize,
 }
 
+/// Pricing for input consisting of ABI-encoded `bytes` arguments,
+/// charged per word of each argument.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+pub struct AbiBytes {
+	/// Base price.
+	pub base: usize,
+	/// Price for word of each argument, in argument order.
+	pub words: Vec<usize>,
+}
+
+/// Pricing for zkSNARK verification, charged per public input.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+pub struct PublicInputs {
+	/// Base price.
+	pub base: usize,
+	/// Price for each public input.
+	pub input: usize,
+}
+
+/// Pricing for the alt_bn128 pairing check, charged per pair of points.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+pub struct AltBn128Pairing {
+	/// Base price.
+	pub base: usize,
+	/// Price for each (G1, G2) pair.
+	pub pair: usize,
+}
+
+/// Pricing for modular exponentiation, based on the complexity of multiplying
+/// numbers of the input's size.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+pub struct Modexp {
+	/// Divisor applied to the complexity.
+	pub divisor: usize,
+}
+
 /// Pricing variants.
 #[derive(Debug, PartialEq, Deserialize, Clone)]
 pub enum Pricing {
 	/// Linear pricing.
 	#[serde(rename="linear")]
 	Linear(Linear),
+	/// Pricing by length of ABI-encoded `bytes` arguments.
+	#[serde(rename="abiBytes")]
+	AbiBytes(AbiBytes),
+	/// Pricing by number of zkSNARK public inputs.
+	#[serde(rename="publicInputs")]
+	PublicInputs(PublicInputs),
+	/// Pricing by number of alt_bn128 pairs.
+	#[serde(rename="altBn128Pairing")]
+	AltBn128Pairing(AltBn128Pairing),
+	/// Pricing by modular exponentiation complexity.
+	#[serde(rename="modexp")]
+	Modexp(Modexp),
 }
 
 /// Spec builtin.
@@ -40,12 +90,16 @@ pub struct Builtin {
 	pub name: String,
 	/// Builtin pricing.
 	pub pricing: Pricing,
+	/// Block number at which the builtin becomes active.
+	#[serde(rename="activateAt")]
+	pub activate_at: Option<Uint>,
 }
 
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use spec::builtin::Builtin;
+	use uint::Uint;
+	use spec::builtin::{Builtin, Pricing, AbiBytes, PublicInputs, AltBn128Pairing, Modexp};
 
 	#[test]
 	fn builtin_deserialization() {
@@ -56,4 +110,64 @@ mod tests {
 		let _deserialized: Builtin = serde_json::from_str(s).unwrap();
 		// TODO: validate all fields
 	}
+
+	#[test]
+	fn builtin_abi_bytes_deserialization() {
+		let s = r#"{
+			"name": "zkSNARK",
+			"pricing": { "abiBytes": { "base": 100000, "words": [200, 50, 100] } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::AbiBytes(AbiBytes {
+			base: 100000,
+			words: vec![200, 50, 100],
+		}));
+	}
+
+	#[test]
+	fn builtin_public_inputs_deserialization() {
+		let s = r#"{
+			"name": "zkSNARK",
+			"pricing": { "publicInputs": { "base": 100000, "input": 5000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::PublicInputs(PublicInputs {
+			base: 100000,
+			input: 5000,
+		}));
+	}
+
+	#[test]
+	fn builtin_alt_bn128_pairing_deserialization() {
+		let s = r#"{
+			"name": "alt_bn128_pairing",
+			"pricing": { "altBn128Pairing": { "base": 100000, "pair": 80000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::AltBn128Pairing(AltBn128Pairing {
+			base: 100000,
+			pair: 80000,
+		}));
+	}
+
+	#[test]
+	fn builtin_modexp_deserialization() {
+		let s = r#"{
+			"name": "modexp",
+			"pricing": { "modexp": { "divisor": 20 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Modexp(Modexp { divisor: 20 }));
+	}
+
+	#[test]
+	fn builtin_activate_at_deserialization() {
+		let s = r#"{
+			"name": "zkSNARK",
+			"activateAt": "0x0f4240",
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.activate_at, Some(Uint(1_000_000.into())));
+	}
 }
@@ -28,9 +28,9 @@ pub mod ethash;
 pub mod basic_authority;
 
 pub use self::account::Account;
-pub use self::builtin::{Builtin, Pricing, Linear};
+pub use self::builtin::{Builtin, Pricing, Linear, AbiBytes, PublicInputs, AltBn128Pairing, Modexp};
 pub use self::genesis::Genesis;
-pub use self::params::Params;
+pub use self::params::{Params, SnarkRegistry};
 pub use self::spec::Spec;
 pub use self::seal::{Seal, Ethereum, Generic};
 pub use self::engine::Engine;
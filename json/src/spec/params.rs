@@ -17,6 +17,19 @@
 //! Spec params deserialization.
 
 use uint::Uint;
+use hash::Address;
+
+/// zkSNARK verification key registry params.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct SnarkRegistry {
+	/// Address of the registry; verification keys are kept in its storage.
+	pub address: Address,
+	/// Address of the `zkSNARK` builtin used to verify proofs against registered keys.
+	pub verifier: Address,
+	/// Number of the first block in which the registry is active.
+	#[serde(rename="activateAt")]
+	pub activate_at: Option<Uint>,
+}
 
 /// Spec params.
 #[derive(Debug, PartialEq, Deserialize)]
@@ -33,12 +46,20 @@ pub struct Params {
 	/// Minimum gas limit.
 	#[serde(rename="minGasLimit")]
 	pub min_gas_limit: Uint,
+	/// zkSNARK verification key registry.
+	#[serde(rename="zkSNARKRegistry")]
+	pub zksnark_registry: Option<SnarkRegistry>,
+	/// Number of first block where EIP-155 replay-protected transactions are accepted.
+	#[serde(rename="eip155Transition")]
+	pub eip155_transition: Option<Uint>,
 }
 
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use spec::params::Params;
+	use spec::params::{Params, SnarkRegistry};
+	use hash::Address;
+	use uint::Uint;
 
 	#[test]
 	fn params_deserialization() {
@@ -53,4 +74,41 @@ mod tests {
 		let _deserialized: Params = serde_json::from_str(s).unwrap();
 		// TODO: validate all fields
 	}
+
+	#[test]
+	fn params_zksnark_registry_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID" : "0x1",
+			"minGasLimit": "0x1388",
+			"accountStartNonce": "0x00",
+			"zkSNARKRegistry": {
+				"address": "0x0000000000000000000000000000000000000100",
+				"verifier": "0x0000000000000000000000000000000000000005",
+				"activateAt": "0x64"
+			}
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.zksnark_registry, Some(SnarkRegistry {
+			address: Address(0x100.into()),
+			verifier: Address(0x5.into()),
+			activate_at: Some(Uint(100.into())),
+		}));
+	}
+
+	#[test]
+	fn params_eip155_transition_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID" : "0x11",
+			"minGasLimit": "0x1388",
+			"accountStartNonce": "0x00",
+			"eip155Transition": "0x0a"
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		let transition: u64 = deserialized.eip155_transition.unwrap().into();
+		assert_eq!(transition, 10);
+	}
 }
@@ -17,7 +17,7 @@
 //! Lenient uint json deserialization for test json files.
 
 use std::str::FromStr;
-use serde::{Deserialize, Deserializer, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, Error};
 use serde::de::Visitor;
 use util::numbers::{U256, Uint as U};
 
@@ -49,6 +49,13 @@ impl Into<u8> for Uint {
 	}
 }
 
+impl Serialize for Uint {
+	fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error>
+		where S: Serializer {
+		serializer.serialize_str(&format!("0x{:x}", self.0))
+	}
+}
+
 impl Deserialize for Uint {
 	fn deserialize<D>(deserializer: &mut D) -> Result<Self, D::Error>
 		where D: Deserializer {
@@ -104,6 +111,12 @@ mod test {
 		]);
 	}
 
+	#[test]
+	fn uint_serialization() {
+		let serialized = serde_json::to_string(&vec![Uint(U256::from(10)), Uint(U256::from(0))]).unwrap();
+		assert_eq!(serialized, r#"["0xa","0x0"]"#);
+	}
+
 	#[test]
 	fn uint_into() {
 		assert_eq!(U256::from(10), Uint(U256::from(10)).into());
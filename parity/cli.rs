@@ -28,6 +28,7 @@ Usage:
   parity wallet import <path> --password FILE [options]
   parity import [ <file> ] [options]
   parity export [ <file> ] [options]
+  parity export-state [ <file> ] [options]
   parity signer new-token [options]
   parity [options]
   parity ui [options]
@@ -47,8 +48,8 @@ Operating Options:
                            [default: 3600].
   --chain CHAIN            Specify the blockchain type. CHAIN may be either a
                            JSON chain specification file or olympic, frontier,
-                           homestead, mainnet, morden, homestead-dogmatic, or
-                           testnet [default: homestead].
+                           homestead, mainnet, morden, homestead-dogmatic,
+                           testnet or dev [default: homestead].
   -d --db-path PATH        Specify the database & configuration directory path
                            [default: $HOME/.parity].
   --keys-path PATH         Specify the path for JSON key files to be found
@@ -105,7 +106,7 @@ API and Console Options:
   --jsonrpc-apis APIS      Specify the APIs available through the JSONRPC
                            interface. APIS is a comma-delimited list of API
                            name. Possible name are web3, eth, net, personal,
-                           ethcore, ethcore_set, traces.
+                           ethcore, ethcore_set, traces, debug, txpool.
                            [default: web3,eth,net,ethcore,personal,traces].
   --jsonrpc-hosts HOSTS    List of allowed Host header values. This option will
                            validate the Host header sent by the browser, it
@@ -182,12 +183,29 @@ Sealing/Mining Options:
                            more than 32 characters.
   --tx-queue-size LIMIT    Maximum amount of transactions in the queue (waiting
                            to be included in next block) [default: 1024].
+  --tx-queue-per-sender N  Maximum amount of transactions from a single
+                           sender in the queue. Local transactions are not
+                           affected [default: 64].
+  --tx-queue-gas GAS       Maximum total gas of transactions in the queue.
+                           Cheapest transactions of senders with most
+                           transactions are dropped first when exceeded.
+  --tx-replace-bump PCT    Minimal gas price increase (in percents) required
+                           for a transaction to replace a queued one with
+                           the same sender and nonce [default: 10].
   --remove-solved          Move solved blocks from the work package queue
                            instead of cloning them. This gives a slightly
                            faster import speed, but means that extra solutions
                            submitted for the same work package will go unused.
   --notify-work URLS       URLs to which work package notifications are pushed.
                            URLS should be a comma-delimited list of HTTP URLs.
+  --mining-threads N       Number of threads mining sealing work with the CPU.
+                           Requires an Ethash chain and is only useful for
+                           small private ones; 0 disables the internal miner
+                           [default: 0].
+  --mining-dag             Let the internal miner use the full Ethash dataset
+                           (DAG) instead of the light cache. Mines much faster,
+                           but needs over 1GB of memory and takes a while to
+                           generate the DAG for every epoch.
 
 Footprint Options:
   --tracing BOOL           Indicates if full transaction tracing should be
@@ -216,7 +234,9 @@ Database Options:
   --db-compaction TYPE     Database compaction type. TYPE may be one of:
                            ssd - suitable for SSDs and fast HDDs;
                            hdd - suitable for slow HDDs [default: ssd].
-  --fat-db                 Fat database.
+  --fat-db                 Fat database: keeps key preimages so that accounts
+                           and storage can be enumerated (requires
+                           --pruning=archive).
 
 Import/Export Options:
   --from BLOCK             Export from block BLOCK, which may be an index or
@@ -225,6 +245,9 @@ Import/Export Options:
                            index, hash or 'latest' [default: latest].
   --format FORMAT          For import/export in given format. FORMAT must be
                            one of 'hex' and 'binary'.
+  --at BLOCK               Export state at block BLOCK, which may be an index,
+                           hash or 'latest'. Requires --fat-db
+                           [default: latest].
 
 Virtual Machine Options:
   --jitvm                  Enable the JIT VM.
@@ -279,6 +302,7 @@ pub struct Args {
 	pub cmd_new: bool,
 	pub cmd_list: bool,
 	pub cmd_export: bool,
+	pub cmd_export_state: bool,
 	pub cmd_import: bool,
 	pub cmd_signer: bool,
 	pub cmd_new_token: bool,
@@ -346,12 +370,18 @@ pub struct Args {
 	pub flag_gas_cap: String,
 	pub flag_extra_data: Option<String>,
 	pub flag_tx_queue_size: usize,
+	pub flag_tx_queue_per_sender: usize,
+	pub flag_tx_queue_gas: Option<String>,
+	pub flag_tx_replace_bump: usize,
 	pub flag_notify_work: Option<String>,
+	pub flag_mining_threads: usize,
+	pub flag_mining_dag: bool,
 	pub flag_logging: Option<String>,
 	pub flag_version: bool,
 	pub flag_from: String,
 	pub flag_to: String,
 	pub flag_format: Option<String>,
+	pub flag_at: String,
 	pub flag_jitvm: bool,
 	pub flag_log_file: Option<String>,
 	pub flag_no_color: bool,
@@ -32,6 +32,7 @@ use ethcore::client::{append_path, get_db_path, Mode, ClientConfig, DatabaseComp
 use ethcore::miner::{MinerOptions, PendingSet, GasPricer, GasPriceCalibratorOptions};
 use ethcore::ethereum;
 use ethcore::spec::Spec;
+use ethcore::builtin::BuiltinRegistry;
 use ethsync::SyncConfig;
 use rpc::IpcConfiguration;
 use ethcore_logger::Settings as LogSettings;
@@ -106,6 +107,9 @@ impl Configuration {
 			reseal_on_own_tx: own,
 			tx_gas_limit: self.args.flag_tx_gas_limit.as_ref().map_or(!U256::zero(), |d| Self::decode_u256(d, "--tx-gas-limit")),
 			tx_queue_size: self.args.flag_tx_queue_size,
+			tx_queue_per_sender: self.args.flag_tx_queue_per_sender,
+			tx_queue_gas_limit: self.args.flag_tx_queue_gas.as_ref().map_or(!U256::zero(), |d| Self::decode_u256(d, "--tx-queue-gas")),
+			tx_replace_bump: self.args.flag_tx_replace_bump,
 			pending_set: match self.args.flag_relay_set.as_str() {
 				"cheap" => PendingSet::AlwaysQueue,
 				"strict" => PendingSet::AlwaysSealing,
@@ -204,9 +208,12 @@ impl Configuration {
 			"homestead-dogmatic" => ethereum::new_frontier_dogmatic(),
 			"morden" | "testnet" => ethereum::new_morden(),
 			"olympic" => ethereum::new_olympic(),
-			f => Spec::load(contents(f).unwrap_or_else(|_| {
+			"dev" => Spec::new_instant(),
+			f => Spec::load_with_registry(contents(f).unwrap_or_else(|_| {
 				die!("{}: Couldn't read chain specification file. Sure it exists?", f)
-			}).as_ref()),
+			}).as_ref(), &BuiltinRegistry::default()).unwrap_or_else(|e| {
+				die!("{}: Invalid chain specification: {}", f, e)
+			}),
 		}
 	}
 
@@ -88,11 +88,11 @@ use ctrlc::CtrlC;
 use util::{H256, ToPretty, PayloadInfo, Bytes, Colour, version, journaldb, RotatingLogger};
 use util::panics::{MayPanic, ForwardPanic, PanicHandler};
 use ethcore::client::{BlockID, BlockChainClient, ClientConfig, get_db_path, BlockImportError, Mode};
-use ethcore::error::{ImportError};
+use ethcore::error::{ImportError, ListError};
 use ethcore::service::ClientService;
 use ethcore::spec::Spec;
 use ethsync::{NetworkConfiguration};
-use ethcore::miner::{Miner, MinerService, ExternalMiner};
+use ethcore::miner::{Miner, MinerService, ExternalMiner, CpuMiner};
 use migration::migrate;
 use informant::Informant;
 use util::{Mutex, Condvar};
@@ -155,6 +155,11 @@ fn execute(conf: Configuration) {
 		return;
 	}
 
+	if conf.args.cmd_export_state {
+		execute_export_state(conf, panic_handler);
+		return;
+	}
+
 	if conf.args.cmd_import {
 		execute_import(conf, panic_handler);
 		return;
@@ -220,6 +225,11 @@ fn execute_client(conf: Configuration, spec: Spec, client_config: ClientConfig,
 		warn!("NOTE that Signer will not ask you to confirm transactions from unlocked account.");
 	}
 
+	// The internal miner only searches for Ethash seals
+	if conf.args.flag_mining_threads > 0 && spec.engine.name() != "Ethash" {
+		die!("--mining-threads requires an Ethash chain, but the chosen chain is sealed by {}.", spec.engine.name());
+	}
+
 	let net_settings = conf.net_settings(&spec);
 	let sync_config = conf.sync_config(&spec);
 
@@ -248,6 +258,12 @@ fn execute_client(conf: Configuration, spec: Spec, client_config: ClientConfig,
 	let external_miner = Arc::new(ExternalMiner::default());
 	let network_settings = Arc::new(conf.network_settings());
 
+	// Internal CPU miner
+	let _cpu_miner = match conf.args.flag_mining_threads {
+		0 => None,
+		threads => Some(CpuMiner::start(&client, &miner, external_miner.clone(), threads, conf.args.flag_mining_dag)),
+	};
+
 	// Sync
 	let (sync_provider, manage_network, chain_notify) =
 		modules::sync(&mut hypervisor, sync_config, NetworkConfiguration::from(net_settings), client.clone(), &conf.log_settings())
@@ -402,6 +418,53 @@ fn execute_export(conf: Configuration, panic_handler: Arc<PanicHandler>) {
 	}
 }
 
+fn execute_export_state(conf: Configuration, panic_handler: Arc<PanicHandler>) {
+	let spec = conf.spec();
+	let client_config = conf.client_config(&spec);
+	if !conf.args.flag_fat_db {
+		die!("State export requires key preimages. Please re-run with --fat-db --pruning=archive");
+	}
+
+	// Build client
+	let service = ClientService::start(
+		client_config, spec, Path::new(&conf.path()), Arc::new(Miner::with_spec(conf.spec()))
+	).unwrap_or_else(|e| die_with_error("Client", e));
+
+	panic_handler.forward_from(&service);
+	let client = service.client();
+
+	let at = conf.args.flag_at.as_str();
+	let id = if at == "latest" {
+		BlockID::Latest
+	} else if let Ok(n) = at.parse::<u64>() {
+		BlockID::Number(n)
+	} else if let Ok(h) = H256::from_str(at) {
+		BlockID::Hash(h)
+	} else {
+		die!("Invalid --at parameter given: {:?}", at);
+	};
+	let state = client.state_at(id).unwrap_or_else(|| die!("State of block {:?} is not available", at));
+
+	let mut out: Box<Write> = if let Some(f) = conf.args.arg_file {
+		Box::new(File::create(&f).unwrap_or_else(|_| die!("Cannot write to file given: {}", f)))
+	} else {
+		Box::new(::std::io::stdout())
+	};
+
+	// stream the accounts as a JSON object in the format of chain spec `accounts`.
+	out.write_fmt(format_args!("{{")).expect("Couldn't write to stream.");
+	let mut first = true;
+	state.for_each_pod_account(|address, account| {
+		let separator = if first { "" } else { "," };
+		first = false;
+		out.write_fmt(format_args!("{}\n\"0x{}\": {}", separator, address.hex(), account.to_json())).expect("Couldn't write to stream.");
+	}).unwrap_or_else(|e| match e {
+		ListError::MissingPreimage(hash) => die!("Cannot export state: the database has no preimage for key hash {:?}. Exporting state requires a database synced with --fat-db from genesis.", hash),
+		ListError::Unavailable => die!("Cannot export state: account listing is only available with --fat-db."),
+	});
+	out.write_fmt(format_args!("\n}}\n")).expect("Couldn't write to stream.");
+}
+
 fn execute_import(conf: Configuration, panic_handler: Arc<PanicHandler>) {
 	let spec = conf.spec();
 	let client_config = conf.client_config(&spec);
@@ -15,6 +15,8 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use util::{Address, U256};
+use ethcore::client::StateOverride;
+use ethcore::miner::TransactionCondition;
 
 /// Transaction request coming from RPC
 #[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
@@ -33,6 +35,8 @@ pub struct TransactionRequest {
 	pub data: Option<Vec<u8>>,
 	/// Transaction's nonce
 	pub nonce: Option<U256>,
+	/// Condition under which the transaction is released to the queue
+	pub condition: Option<TransactionCondition>,
 }
 
 /// Transaction confirmation waiting in a queue
@@ -61,4 +65,6 @@ pub struct CallRequest {
 	pub data: Option<Vec<u8>>,
 	/// Nonce
 	pub nonce: Option<U256>,
+	/// Overrides of the state the call is made on
+	pub state_override: StateOverride,
 }
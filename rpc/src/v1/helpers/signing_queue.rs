@@ -325,6 +325,7 @@ mod test {
 			value: Some(U256::from(10_000_000)),
 			data: None,
 			nonce: None,
+			condition: None,
 		}
 	}
 
@@ -0,0 +1,66 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Debug rpc implementation.
+
+use std::sync::{Weak, Arc};
+use jsonrpc_core::*;
+use ethcore::client::{BlockChainClient, TransactionID};
+use ethcore::error::CallError;
+use v1::traits::Debug;
+use v1::impls::{state_pruned, execution_error};
+use v1::types::{H256, TraceOptions, DebugTrace};
+
+/// Debug rpc implementation.
+pub struct DebugClient<C> where C: BlockChainClient {
+	client: Weak<C>,
+}
+
+impl<C> DebugClient<C> where C: BlockChainClient {
+	/// Creates new Debug client.
+	pub fn new(client: &Arc<C>) -> Self {
+		DebugClient {
+			client: Arc::downgrade(client),
+		}
+	}
+
+	fn active(&self) -> Result<(), Error> {
+		take_weak!(self.client).keep_alive();
+		Ok(())
+	}
+}
+
+impl<C> Debug for DebugClient<C> where C: BlockChainClient + 'static {
+	fn trace_transaction(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		let has_options = match params {
+			Params::Array(ref vec) => vec.len() > 1,
+			_ => false,
+		};
+		let params = match has_options {
+			true => from_params::<(H256, TraceOptions)>(params),
+			false => from_params::<(H256,)>(params).map(|(hash,)| (hash, TraceOptions::default())),
+		};
+		params.and_then(|(transaction_hash, options)| {
+			match take_weak!(self.client).replay_struct_logs(TransactionID::Hash(transaction_hash.into()), options.into()) {
+				Ok(trace) => to_value(&DebugTrace::from(trace)),
+				Err(CallError::TransactionNotFound) | Err(CallError::BlockNotFound) => Ok(Value::Null),
+				Err(CallError::StatePruned) => Err(state_pruned()),
+				Err(e) => Err(execution_error(e)),
+			}
+		})
+	}
+}
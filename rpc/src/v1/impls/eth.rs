@@ -39,12 +39,13 @@ use ethcore::views::*;
 use ethcore::ethereum::Ethash;
 use ethcore::transaction::{Transaction as EthTransaction, SignedTransaction, Action};
 use ethcore::log_entry::LogEntry;
+use ethcore::error::CallError;
 use ethcore::filter::Filter as EthcoreFilter;
 use self::ethash::SeedHashCompute;
 use v1::traits::Eth;
-use v1::types::{Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo, Transaction, CallRequest, Index, Filter, Log, Receipt, H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256};
+use v1::types::{AccountProof, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo, Transaction, CallRequest, Index, Filter, Log, Receipt, H64 as RpcH64, H256 as RpcH256, H160 as RpcH160, U256 as RpcU256};
 use v1::helpers::CallRequest as CRequest;
-use v1::impls::{default_gas_price, dispatch_transaction, error_codes};
+use v1::impls::{default_gas_price, dispatch_transaction, error_codes, block_not_found, state_pruned};
 use serde;
 
 /// Eth rpc implementation.
@@ -383,6 +384,19 @@ impl<C, S: ?Sized, M, EM> Eth for EthClient<C, S, M, EM> where
 
 	}
 
+	fn proof(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		from_params_default_third::<RpcH160, Vec<RpcH256>>(params)
+			.and_then(|(address, keys, block_number,)| {
+				let address: Address = RpcH160::into(address);
+				let keys = keys.into_iter().map(Into::into).collect();
+				match take_weak!(self.client).prove_account(&address, keys, block_number.into()) {
+					Some(proof) => to_value(&AccountProof::from(proof)),
+					None => Err(state_pruned()),
+				}
+			})
+	}
+
 	fn transaction_count(&self, params: Params) -> Result<Value, Error> {
 		try!(self.active());
 		from_params_default_second(params)
@@ -625,13 +639,18 @@ impl<C, S: ?Sized, M, EM> Eth for EthClient<C, S, M, EM> where
 		from_params_default_second(params)
 			.and_then(|(request, block_number,)| {
 				let request = CallRequest::into(request);
+				let overrides = request.state_override.clone();
 				let signed = try!(self.sign_call(request));
 				let r = match block_number {
-					BlockNumber::Pending => take_weak!(self.miner).call(take_weak!(self.client).deref(), &signed, Default::default()),
-					BlockNumber::Latest => take_weak!(self.client).call(&signed, Default::default()),
-					_ => panic!("{:?}", block_number),
+					BlockNumber::Pending => take_weak!(self.miner).call(take_weak!(self.client).deref(), &signed, &overrides, Default::default()),
+					block_number => take_weak!(self.client).call(&signed, block_number.into(), &overrides, Default::default()),
 				};
-				to_value(&r.map(|e| Bytes(e.output)).unwrap_or(Bytes::new(vec![])))
+				match r {
+					Ok(executed) => to_value(&Bytes(executed.output)),
+					Err(CallError::BlockNotFound) => Err(block_not_found()),
+					Err(CallError::StatePruned) => Err(state_pruned()),
+					Err(_) => to_value(&Bytes::new(vec![])),
+				}
 			})
 	}
 
@@ -640,13 +659,18 @@ impl<C, S: ?Sized, M, EM> Eth for EthClient<C, S, M, EM> where
 		from_params_default_second(params)
 			.and_then(|(request, block_number,)| {
 				let request = CallRequest::into(request);
+				let overrides = request.state_override.clone();
 				let signed = try!(self.sign_call(request));
 				let r = match block_number {
-					BlockNumber::Pending => take_weak!(self.miner).call(take_weak!(self.client).deref(), &signed, Default::default()),
-					BlockNumber::Latest => take_weak!(self.client).call(&signed, Default::default()),
-					_ => return Err(Error::invalid_params()),
+					BlockNumber::Pending => take_weak!(self.miner).call(take_weak!(self.client).deref(), &signed, &overrides, Default::default()),
+					block_number => take_weak!(self.client).call(&signed, block_number.into(), &overrides, Default::default()),
 				};
-				to_value(&RpcU256::from(r.map(|res| res.gas_used + res.refunded).unwrap_or(From::from(0))))
+				match r {
+					Ok(executed) => to_value(&RpcU256::from(executed.gas_used + executed.refunded)),
+					Err(CallError::BlockNotFound) => Err(block_not_found()),
+					Err(CallError::StatePruned) => Err(state_pruned()),
+					Err(_) => to_value(&RpcU256::from(0)),
+				}
 			})
 	}
 
@@ -24,10 +24,14 @@ use std::collections::{BTreeMap};
 use ethcore::client::{MiningBlockChainClient};
 use jsonrpc_core::*;
 use ethcore::miner::MinerService;
+use ethcore::error::ListError;
 use v1::traits::Ethcore;
-use v1::types::{Bytes, U256};
+use v1::types::{Bytes, U256, H160, H256, BlockNumber};
 use v1::helpers::{SigningQueue, ConfirmationsQueue};
-use v1::impls::error_codes;
+use v1::impls::{error_codes, missing_preimage};
+
+/// Maximal number of accounts or storage keys returned by a single listing request.
+const MAX_LIST_COUNT: u64 = 1000;
 
 /// Ethcore implementation.
 pub struct EthcoreClient<C, M> where
@@ -160,4 +164,48 @@ impl<C, M> Ethcore for EthcoreClient<C, M> where M: MinerService + 'static, C: M
 			Some(ref queue) => to_value(&queue.len()),
 		}
 	}
+
+	fn list_accounts(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		let has_block = match params {
+			Params::Array(ref vec) => vec.len() > 2,
+			_ => false,
+		};
+		let params = match has_block {
+			true => from_params::<(u64, Option<H160>, BlockNumber)>(params),
+			false => from_params::<(u64, Option<H160>)>(params).map(|(count, after)| (count, after, BlockNumber::Latest)),
+		};
+		params.and_then(|(count, after, block_number)| {
+			if count > MAX_LIST_COUNT {
+				return Err(Error::invalid_params());
+			}
+			match take_weak!(self.client).list_accounts(block_number.into(), after.map(Into::into), count) {
+				Ok(accounts) => to_value(&accounts.into_iter().map(H160::from).collect::<Vec<_>>()),
+				Err(ListError::MissingPreimage(hash)) => Err(missing_preimage(hash.into())),
+				Err(ListError::Unavailable) => Ok(Value::Null),
+			}
+		})
+	}
+
+	fn list_storage_keys(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		let has_block = match params {
+			Params::Array(ref vec) => vec.len() > 3,
+			_ => false,
+		};
+		let params = match has_block {
+			true => from_params::<(H160, u64, Option<H256>, BlockNumber)>(params),
+			false => from_params::<(H160, u64, Option<H256>)>(params).map(|(address, count, after)| (address, count, after, BlockNumber::Latest)),
+		};
+		params.and_then(|(address, count, after, block_number)| {
+			if count > MAX_LIST_COUNT {
+				return Err(Error::invalid_params());
+			}
+			match take_weak!(self.client).list_storage(block_number.into(), &address.into(), after.map(Into::into), count) {
+				Ok(keys) => to_value(&keys.into_iter().map(H256::from).collect::<Vec<_>>()),
+				Err(ListError::MissingPreimage(hash)) => Err(missing_preimage(hash.into())),
+				Err(ListError::Unavailable) => Ok(Value::Null),
+			}
+		})
+	}
 }
@@ -39,7 +39,9 @@ mod personal_signer;
 mod ethcore;
 mod ethcore_set;
 mod traces;
+mod debug;
 mod rpc;
+mod txpool;
 
 pub use self::web3::Web3Client;
 pub use self::eth::EthClient;
@@ -51,12 +53,14 @@ pub use self::personal_signer::SignerClient;
 pub use self::ethcore::EthcoreClient;
 pub use self::ethcore_set::EthcoreSetClient;
 pub use self::traces::TracesClient;
+pub use self::debug::DebugClient;
 pub use self::rpc::RpcClient;
+pub use self::txpool::TxpoolClient;
 
 use v1::helpers::TransactionRequest;
 use v1::types::H256 as NH256;
-use ethcore::error::Error as EthcoreError;
-use ethcore::miner::MinerService;
+use ethcore::error::{Error as EthcoreError, CallError};
+use ethcore::miner::{MinerService, TransactionCondition};
 use ethcore::client::MiningBlockChainClient;
 use ethcore::transaction::{Action, SignedTransaction, Transaction};
 use ethcore::account_provider::{AccountProvider, Error as AccountError};
@@ -70,8 +74,10 @@ mod error_codes {
 	pub const UNSUPPORTED_REQUEST_CODE: i64 = -32000;
 	pub const NO_WORK_CODE: i64 = -32001;
 	pub const NO_AUTHOR_CODE: i64 = -32002;
+	pub const UNKNOWN_BLOCK: i64 = -32003;
 	pub const UNKNOWN_ERROR: i64 = -32009;
 	pub const TRANSACTION_ERROR: i64 = -32010;
+	pub const EXECUTION_ERROR: i64 = -32015;
 	pub const ACCOUNT_LOCKED: i64 = -32020;
 	pub const PASSWORD_INVALID: i64 = -32021;
 	pub const SIGNER_DISABLED: i64 = -32030;
@@ -88,6 +94,19 @@ fn dispatch_transaction<C, M>(client: &C, miner: &M, signed_transaction: SignedT
 		.and_then(|_| to_value(&hash))
 }
 
+fn dispatch_conditional_transaction<C, M>(client: &C, miner: &M, signed_transaction: SignedTransaction, condition: Option<TransactionCondition>) -> Result<Value, Error>
+	where C: MiningBlockChainClient, M: MinerService {
+	let condition = match condition {
+		Some(condition) => condition,
+		None => return dispatch_transaction(client, miner, signed_transaction),
+	};
+	let hash = NH256::from(signed_transaction.hash());
+
+	miner.import_conditional_transaction(client, signed_transaction, condition)
+		.map_err(transaction_error)
+		.and_then(|_| to_value(&hash))
+}
+
 fn prepare_transaction<C, M>(client: &C, miner: &M, request: TransactionRequest) -> Transaction where C: MiningBlockChainClient, M: MinerService {
 	Transaction {
 		nonce: request.nonce
@@ -107,29 +126,33 @@ fn prepare_transaction<C, M>(client: &C, miner: &M, request: TransactionRequest)
 fn unlock_sign_and_dispatch<C, M>(client: &C, miner: &M, request: TransactionRequest, account_provider: &AccountProvider, address: Address, password: String) -> Result<Value, Error>
 	where C: MiningBlockChainClient, M: MinerService {
 
+	let condition = request.condition;
 	let signed_transaction = {
 		let t = prepare_transaction(client, miner, request);
-		let hash = t.hash();
+		let chain_id = client.signing_chain_id();
+		let hash = t.hash_with_chain_id(chain_id);
 		let signature = try!(account_provider.sign_with_password(address, password, hash).map_err(password_error));
-		t.with_signature(signature)
+		t.with_signature_with_chain_id(signature, chain_id)
 	};
 
 	trace!(target: "miner", "send_transaction: dispatching tx: {}", encode(&signed_transaction).to_vec().pretty());
-	dispatch_transaction(&*client, &*miner, signed_transaction)
+	dispatch_conditional_transaction(&*client, &*miner, signed_transaction, condition)
 }
 
 fn sign_and_dispatch<C, M>(client: &C, miner: &M, request: TransactionRequest, account_provider: &AccountProvider, address: Address) -> Result<Value, Error>
 	where C: MiningBlockChainClient, M: MinerService {
 
+	let condition = request.condition;
 	let signed_transaction = {
 		let t = prepare_transaction(client, miner, request);
-		let hash = t.hash();
+		let chain_id = client.signing_chain_id();
+		let hash = t.hash_with_chain_id(chain_id);
 		let signature = try!(account_provider.sign(address, hash).map_err(signing_error));
-		t.with_signature(signature)
+		t.with_signature_with_chain_id(signature, chain_id)
 	};
 
 	trace!(target: "miner", "send_transaction: dispatching tx: {}", encode(&signed_transaction).to_vec().pretty());
-	dispatch_transaction(&*client, &*miner, signed_transaction)
+	dispatch_conditional_transaction(&*client, &*miner, signed_transaction, condition)
 }
 
 fn default_gas_price<C, M>(client: &C, miner: &M) -> U256 where C: MiningBlockChainClient, M: MinerService {
@@ -140,6 +163,38 @@ fn default_gas_price<C, M>(client: &C, miner: &M) -> U256 where C: MiningBlockCh
 }
 
 
+fn state_pruned() -> Error {
+	Error {
+		code: ErrorCode::ServerError(error_codes::UNSUPPORTED_REQUEST_CODE),
+		message: "State at the requested block is not available because it has been pruned. Run with --pruning=archive to keep historical state.".into(),
+		data: None,
+	}
+}
+
+fn missing_preimage(hash: NH256) -> Error {
+	Error {
+		code: ErrorCode::ServerError(error_codes::UNSUPPORTED_REQUEST_CODE),
+		message: "State DB lacks the preimages of some keys; it was created by a version which didn't record them. Resync with --fat-db to list keys.".into(),
+		data: Some(Value::String(format!("0x{:?}", hash))),
+	}
+}
+
+fn block_not_found() -> Error {
+	Error {
+		code: ErrorCode::ServerError(error_codes::UNKNOWN_BLOCK),
+		message: "Requested block is not known.".into(),
+		data: None,
+	}
+}
+
+fn execution_error(error: CallError) -> Error {
+	Error {
+		code: ErrorCode::ServerError(error_codes::EXECUTION_ERROR),
+		message: "Transaction could not be replayed.".into(),
+		data: Some(Value::String(format!("{}", error))),
+	}
+}
+
 fn signing_error(error: AccountError) -> Error {
 	Error {
 		code: ErrorCode::ServerError(error_codes::ACCOUNT_LOCKED),
@@ -163,8 +218,8 @@ fn transaction_error(error: EthcoreError) -> Error {
 		let msg = match e {
 			AlreadyImported => "Transaction with the same hash was already imported.".into(),
 			Old => "Transaction nonce is too low. Try incrementing the nonce.".into(),
-			TooCheapToReplace => {
-				"Transaction fee is too low. There is another transaction with same nonce in the queue. Try increasing the fee or incrementing the nonce.".into()
+			TooCheapToReplace { minimal, got } => {
+				format!("Transaction fee is too low. There is another transaction with same nonce in the queue, replacing it requires gas price of at least {} (got: {}). Try increasing the fee or incrementing the nonce.", minimal, got)
 			},
 			LimitReached => {
 				"There are too many transactions in the queue. Your transaction was dropped due to limit. Try increasing the fee.".into()
@@ -179,6 +234,12 @@ fn transaction_error(error: EthcoreError) -> Error {
 				format!("Transaction cost exceeds current gas limit. Limit: {}, got: {}. Try decreasing supplied gas.", limit, got)
 			},
 			InvalidGasLimit(_) => "Supplied gas is beyond limit.".into(),
+			InvalidChainId { expected: Some(expected), got } => {
+				format!("Transaction is signed for chain {}, but this chain's id is {}.", got, expected)
+			},
+			InvalidChainId { expected: None, got } => {
+				format!("Transaction is signed for chain {}, but replay protection is not active on this chain yet.", got)
+			},
 		};
 		Error {
 			code: ErrorCode::ServerError(error_codes::TRANSACTION_ERROR),
@@ -20,10 +20,12 @@ use std::sync::{Weak, Arc};
 use jsonrpc_core::*;
 use std::collections::BTreeMap;
 //use util::H256;
-use ethcore::client::{BlockChainClient, CallAnalytics, TransactionID, TraceId};
+use ethcore::client::{BlockChainClient, CallAnalytics, Executed, TransactionID, TraceId, BlockID};
+use ethcore::error::CallError;
 use ethcore::miner::MinerService;
 use ethcore::transaction::{Transaction as EthTransaction, SignedTransaction, Action};
 use v1::traits::Traces;
+use v1::impls::state_pruned;
 use v1::helpers::CallRequest as CRequest;
 use v1::types::{TraceFilter, LocalizedTrace, Trace, BlockNumber, Index, CallRequest, Bytes, StateDiff, VMTrace, H256};
 
@@ -119,29 +121,59 @@ impl<C, M> Traces for TracesClient<C, M> where C: BlockChainClient + 'static, M:
 		from_params(params)
 			.and_then(|(request, flags)| {
 				let request = CallRequest::into(request);
-				let flags: Vec<String> = flags;
-				let analytics = CallAnalytics {
-					transaction_tracing: flags.contains(&("trace".to_owned())),
-					vm_tracing: flags.contains(&("vmTrace".to_owned())),
-					state_diffing: flags.contains(&("stateDiff".to_owned())),
-				};
+				let overrides = request.state_override.clone();
 				let signed = try!(self.sign_call(request));
-				let r = take_weak!(self.client).call(&signed, analytics);
-				if let Ok(executed) = r {
-					// TODO maybe add other stuff to this?
-					let mut ret = map!["output".to_owned() => to_value(&Bytes(executed.output)).unwrap()];
-					if let Some(trace) = executed.trace {
-						ret.insert("trace".to_owned(), to_value(&Trace::from(trace)).unwrap());
-					}
-					if let Some(vm_trace) = executed.vm_trace {
-						ret.insert("vmTrace".to_owned(), to_value(&VMTrace::from(vm_trace)).unwrap());
-					}
-					if let Some(state_diff) = executed.state_diff {
-						ret.insert("stateDiff".to_owned(), to_value(&StateDiff::from(state_diff)).unwrap());
-					}
-					return Ok(Value::Object(ret))
+				match take_weak!(self.client).call(&signed, BlockID::Latest, &overrides, to_call_analytics(flags)) {
+					Ok(executed) => Ok(executed_to_value(executed)),
+					_ => Ok(Value::Null),
+				}
+			})
+	}
+
+	fn replay_transaction(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		from_params::<(H256, _)>(params)
+			.and_then(|(transaction_hash, flags)| {
+				match take_weak!(self.client).replay(TransactionID::Hash(transaction_hash.into()), to_call_analytics(flags)) {
+					Ok(executed) => Ok(executed_to_value(executed)),
+					Err(CallError::StatePruned) => Err(state_pruned()),
+					_ => Ok(Value::Null),
 				}
-				Ok(Value::Null)
 			})
 	}
+
+	fn replay_block_transactions(&self, params: Params) -> Result<Value, Error> {
+		try!(self.active());
+		from_params::<(BlockNumber, _)>(params)
+			.and_then(|(block_number, flags)| {
+				match take_weak!(self.client).replay_block_transactions(block_number.into(), to_call_analytics(flags)) {
+					Ok(executed) => Ok(Value::Array(executed.into_iter().map(executed_to_value).collect())),
+					Err(CallError::StatePruned) => Err(state_pruned()),
+					_ => Ok(Value::Null),
+				}
+			})
+	}
+}
+
+fn to_call_analytics(flags: Vec<String>) -> CallAnalytics {
+	CallAnalytics {
+		transaction_tracing: flags.contains(&("trace".to_owned())),
+		vm_tracing: flags.contains(&("vmTrace".to_owned())),
+		state_diffing: flags.contains(&("stateDiff".to_owned())),
+	}
+}
+
+fn executed_to_value(executed: Executed) -> Value {
+	// TODO maybe add other stuff to this?
+	let mut ret = map!["output".to_owned() => to_value(&Bytes(executed.output)).unwrap()];
+	if let Some(trace) = executed.trace {
+		ret.insert("trace".to_owned(), to_value(&Trace::from(trace)).unwrap());
+	}
+	if let Some(vm_trace) = executed.vm_trace {
+		ret.insert("vmTrace".to_owned(), to_value(&VMTrace::from(vm_trace)).unwrap());
+	}
+	if let Some(state_diff) = executed.state_diff {
+		ret.insert("stateDiff".to_owned(), to_value(&StateDiff::from(state_diff)).unwrap());
+	}
+	Value::Object(ret)
 }
@@ -0,0 +1,68 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Txpool rpc implementation.
+
+use std::sync::{Weak, Arc};
+use jsonrpc_core::*;
+use ethcore::client::MiningBlockChainClient;
+use ethcore::miner::MinerService;
+use v1::traits::Txpool;
+use v1::types::{TxpoolContent, TxpoolStatus};
+
+/// Txpool rpc implementation.
+pub struct TxpoolClient<C, M> where
+	C: MiningBlockChainClient,
+	M: MinerService {
+
+	client: Weak<C>,
+	miner: Weak<M>,
+}
+
+impl<C, M> TxpoolClient<C, M> where C: MiningBlockChainClient, M: MinerService {
+	/// Creates new `TxpoolClient`.
+	pub fn new(client: &Arc<C>, miner: &Arc<M>) -> Self {
+		TxpoolClient {
+			client: Arc::downgrade(client),
+			miner: Arc::downgrade(miner),
+		}
+	}
+
+	fn active(&self) -> Result<(), Error> {
+		take_weak!(self.client).keep_alive();
+		Ok(())
+	}
+}
+
+impl<C, M> Txpool for TxpoolClient<C, M> where C: MiningBlockChainClient + 'static, M: MinerService + 'static {
+	fn content(&self, _: Params) -> Result<Value, Error> {
+		try!(self.active());
+		let transactions = take_weak!(self.miner).queued_transactions(&*take_weak!(self.client));
+		to_value(&TxpoolContent::content(transactions))
+	}
+
+	fn status(&self, _: Params) -> Result<Value, Error> {
+		try!(self.active());
+		let transactions = take_weak!(self.miner).queued_transactions(&*take_weak!(self.client));
+		to_value(&TxpoolStatus::from(&transactions[..]))
+	}
+
+	fn inspect(&self, _: Params) -> Result<Value, Error> {
+		try!(self.active());
+		let transactions = take_weak!(self.miner).queued_transactions(&*take_weak!(self.client));
+		to_value(&TxpoolContent::inspect(transactions))
+	}
+}
@@ -18,17 +18,21 @@
 
 use util::{Address, H256, Bytes, U256, FixedHash, Uint};
 use util::standard::*;
-use ethcore::error::{Error, ExecutionError};
-use ethcore::client::{MiningBlockChainClient, Executed, CallAnalytics};
+use ethcore::error::{Error, CallError};
+use ethcore::client::{MiningBlockChainClient, Executed, CallAnalytics, StateOverride};
 use ethcore::block::{ClosedBlock, IsBlock};
 use ethcore::transaction::SignedTransaction;
 use ethcore::receipt::Receipt;
-use ethcore::miner::{MinerService, MinerStatus, TransactionImportResult};
+use ethcore::miner::{MinerService, MinerStatus, TransactionImportResult, TransactionCondition, QueuedTransaction};
 
 /// Test miner service.
 pub struct TestMinerService {
 	/// Imported transactions.
 	pub imported_transactions: Mutex<Vec<SignedTransaction>>,
+	/// Transactions held until their condition is met.
+	pub conditional_transactions: Mutex<Vec<(SignedTransaction, TransactionCondition)>>,
+	/// Transactions reported as queued.
+	pub queued_transactions: Mutex<Vec<QueuedTransaction>>,
 	/// Latest closed block.
 	pub latest_closed_block: Mutex<Option<ClosedBlock>>,
 	/// Pre-existed pending transactions
@@ -50,6 +54,8 @@ impl Default for TestMinerService {
 	fn default() -> TestMinerService {
 		TestMinerService {
 			imported_transactions: Mutex::new(Vec::new()),
+			conditional_transactions: Mutex::new(Vec::new()),
+			queued_transactions: Mutex::new(Vec::new()),
 			latest_closed_block: Mutex::new(None),
 			pending_transactions: Mutex::new(HashMap::new()),
 			pending_receipts: Mutex::new(BTreeMap::new()),
@@ -161,6 +167,17 @@ impl MinerService for TestMinerService {
 		Ok(TransactionImportResult::Current)
 	}
 
+	fn import_conditional_transaction(&self, _chain: &MiningBlockChainClient, transaction: SignedTransaction, condition: TransactionCondition) ->
+		Result<(), Error> {
+
+		self.conditional_transactions.lock().push((transaction, condition));
+		Ok(())
+	}
+
+	fn queued_transactions(&self, _chain: &MiningBlockChainClient) -> Vec<QueuedTransaction> {
+		self.queued_transactions.lock().clone()
+	}
+
 	/// Returns hashes of transactions currently in pending
 	fn pending_transactions_hashes(&self) -> Vec<H256> {
 		vec![]
@@ -215,7 +232,7 @@ impl MinerService for TestMinerService {
 		self.latest_closed_block.lock().as_ref().map_or_else(U256::zero, |b| b.block().fields().state.balance(address).clone())
 	}
 
-	fn call(&self, _chain: &MiningBlockChainClient, _t: &SignedTransaction, _analytics: CallAnalytics) -> Result<Executed, ExecutionError> {
+	fn call(&self, _chain: &MiningBlockChainClient, _t: &SignedTransaction, _overrides: &StateOverride, _analytics: CallAnalytics) -> Result<Executed, CallError> {
 		unimplemented!();
 	}
 
@@ -0,0 +1,111 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use std::collections::BTreeMap;
+use jsonrpc_core::IoHandler;
+use util::hash::H256;
+use util::numbers::U256;
+use ethcore::client::{TestBlockChainClient, Executed, TransactionID, StructLog};
+use ethcore::receipt::LocalizedReceipt;
+use v1::{Debug, DebugClient};
+
+fn debug_client(client: &Arc<TestBlockChainClient>) -> IoHandler {
+	let io = IoHandler::new();
+	io.add_delegate(DebugClient::new(client).to_delegate());
+	io
+}
+
+fn replayable_client() -> Arc<TestBlockChainClient> {
+	let client = Arc::new(TestBlockChainClient::new());
+	client.set_transaction_receipt(TransactionID::Hash(H256::from(1)), LocalizedReceipt {
+		transaction_hash: H256::from(1),
+		transaction_index: 0,
+		block_hash: H256::zero(),
+		block_number: 0,
+		cumulative_gas_used: U256::from(0x5208),
+		gas_used: U256::from(0x5208),
+		contract_address: None,
+		logs: vec![],
+	});
+	client.set_execution_result(Executed {
+		gas: U256::zero(),
+		gas_used: U256::from(0x5208),
+		refunded: U256::zero(),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34],
+		trace: None,
+		vm_trace: None,
+		state_diff: None,
+	});
+	*client.struct_logs.write() = vec![StructLog {
+		pc: 0,
+		op: "STOP".into(),
+		gas: U256::from(100),
+		gas_cost: U256::zero(),
+		depth: 1,
+		stack: Some(vec![U256::from(1)]),
+		memory: Some(vec![]),
+		storage: BTreeMap::new(),
+	}];
+	client
+}
+
+#[test]
+fn rpc_debug_trace_transaction() {
+	let client = replayable_client();
+	let io = debug_client(&client);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000001"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"gas":21000,"returnValue":"1234","structLogs":[{"depth":1,"gas":100,"gasCost":0,"memory":[],"op":"STOP","pc":0,"stack":["0000000000000000000000000000000000000000000000000000000000000001"],"storage":{}}]},"id":1}"#;
+
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_with_options() {
+	let client = replayable_client();
+	let io = debug_client(&client);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000001", {"disableStack": true}], "id": 1}"#;
+
+	assert!(io.handle_request(request).unwrap().contains("structLogs"));
+}
+
+#[test]
+fn rpc_debug_trace_unknown_transaction() {
+	let client = replayable_client();
+	let io = debug_client(&client);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000002"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_failing_replay() {
+	let client = replayable_client();
+	*client.execution_result.write() = None;
+	let io = debug_client(&client);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000001"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"Transaction could not be replayed.","data":"Call error (Transaction execution error (Internal evm error).)."},"id":1}"#;
+
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
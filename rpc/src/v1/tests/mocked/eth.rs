@@ -304,6 +304,37 @@ fn rpc_eth_storage_at() {
 	assert_eq!(tester.io.handle_request(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_proof() {
+	let tester = EthTester::default();
+	tester.client.set_balance(Address::from(1), U256::from(5));
+	tester.client.set_storage(Address::from(1), H256::from(4), H256::from(7));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0x0000000000000000000000000000000000000001", ["0x0000000000000000000000000000000000000000000000000000000000000004"], "latest"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"accountProof":[],"address":"0x0000000000000000000000000000000000000001","balance":"0x05","codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","nonce":"0x00","storageHash":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","storageProof":[{"key":"0x0000000000000000000000000000000000000000000000000000000000000004","proof":[],"value":"0x07"}]},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_proof_pruned() {
+	let tester = EthTester::default();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getProof",
+		"params": ["0x0000000000000000000000000000000000000001", [], "0x10"],
+		"id": 1
+	}"#;
+
+	assert!(tester.io.handle_request(request).unwrap().contains("pruned"));
+}
+
 #[test]
 fn rpc_eth_transaction_count() {
 	let request = r#"{
@@ -489,6 +520,76 @@ fn rpc_eth_call_default_block() {
 	assert_eq!(tester.io.handle_request(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_call_at_historical_block() {
+	let tester = EthTester::default();
+	tester.client.set_execution_result(Executed {
+		gas: U256::zero(),
+		gas_used: U256::from(0xff30),
+		refunded: U256::from(0x5),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34, 0xff],
+		trace: None,
+		vm_trace: None,
+		state_diff: None,
+	});
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"0x0"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x1234ff","id":1}"#;
+
+	assert_eq!(tester.io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_call_at_pruned_block() {
+	let tester = EthTester::default();
+	tester.client.add_blocks(10, EachBlockWith::Nothing);
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"0x0"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"State at the requested block is not available because it has been pruned. Run with --pruning=archive to keep historical state.","data":null},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_call_at_unknown_block() {
+	let tester = EthTester::default();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"0x10"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32003,"message":"Requested block is not known.","data":null},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_estimate_gas() {
 	let tester = EthTester::default();
@@ -241,3 +241,48 @@ fn rpc_ethcore_unsigned_transactions_count_when_signer_disabled() {
 
 	assert_eq!(io.handle_request(request), Some(response.to_owned()));
 }
+
+#[test]
+fn rpc_ethcore_list_accounts() {
+	let miner = miner_service();
+	let client = client_service();
+	client.set_balance(1.into(), 5.into());
+	client.set_balance(2.into(), 10.into());
+	let io = IoHandler::new();
+	io.add_delegate(ethcore_client(&client, &miner).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "ethcore_listAccounts", "params":[1, "0x0000000000000000000000000000000000000001"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":["0x0000000000000000000000000000000000000002"],"id":1}"#;
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "ethcore_listAccounts", "params":[10, null, "0x1"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_ethcore_list_storage_keys() {
+	let miner = miner_service();
+	let client = client_service();
+	client.set_storage(1.into(), 3.into(), 4.into());
+	client.set_storage(1.into(), 5.into(), 6.into());
+	client.set_storage(2.into(), 7.into(), 8.into());
+	let io = IoHandler::new();
+	io.add_delegate(ethcore_client(&client, &miner).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "ethcore_listStorageKeys", "params":["0x0000000000000000000000000000000000000001", 10, null, "latest"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":["0x0000000000000000000000000000000000000000000000000000000000000003","0x0000000000000000000000000000000000000000000000000000000000000005"],"id":1}"#;
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_ethcore_list_accounts_rejects_large_count() {
+	let miner = miner_service();
+	let client = client_service();
+	let io = IoHandler::new();
+	io.add_delegate(ethcore_client(&client, &miner).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "ethcore_listAccounts", "params":[1001, null], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params","data":null},"id":1}"#;
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
@@ -27,3 +27,5 @@ mod ethcore;
 mod ethcore_set;
 mod rpc;
 mod manage_network;
+mod debug;
+mod txpool;
@@ -23,6 +23,7 @@ use v1::{PersonalClient, Personal};
 use v1::tests::helpers::TestMinerService;
 use ethcore::client::TestBlockChainClient;
 use ethcore::transaction::{Action, Transaction};
+use ethcore::miner::TransactionCondition;
 
 struct PersonalTester {
 	accounts: Arc<AccountProvider>,
@@ -140,6 +141,44 @@ fn sign_and_send_transaction_with_invalid_password() {
 	assert_eq!(tester.io.handle_request(request.as_ref()), Some(response.into()));
 }
 
+#[test]
+fn sign_and_send_conditional_transaction() {
+	let tester = setup(None);
+	let address = tester.accounts.new_account("password123").unwrap();
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "personal_signAndSendTransaction",
+		"params": [{
+			"from": ""#.to_owned() + format!("0x{:?}", address).as_ref() + r#"",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000",
+			"value": "0x9184e72a",
+			"condition": { "block": 100 }
+		}, "password123"],
+		"id": 1
+	}"#;
+
+	let t = Transaction {
+		nonce: U256::zero(),
+		gas_price: U256::from(0x9184e72a000u64),
+		gas: U256::from(0x76c0),
+		action: Action::Call(Address::from_str("d46e8dd67c5d32be8058bb8eb970870f07244567").unwrap()),
+		value: U256::from(0x9184e72au64),
+		data: vec![]
+	};
+	tester.accounts.unlock_account_temporarily(address, "password123".into()).unwrap();
+	let signature = tester.accounts.sign(address, t.hash()).unwrap();
+	let t = t.with_signature(signature);
+
+	let response = r#"{"jsonrpc":"2.0","result":""#.to_owned() + format!("0x{:?}", t.hash()).as_ref() + r#"","id":1}"#;
+
+	assert_eq!(tester.io.handle_request(request.as_ref()), Some(response));
+	assert!(tester.miner.imported_transactions.lock().is_empty());
+	assert_eq!(*tester.miner.conditional_transactions.lock(), vec![(t, TransactionCondition::Number(100))]);
+}
+
 #[test]
 fn sign_and_send_transaction() {
 	let tester = setup(None);
@@ -79,11 +79,12 @@ fn should_return_list_of_transactions_in_queue() {
 		value: Some(U256::from(1)),
 		data: None,
 		nonce: None,
+		condition: None,
 	});
 
 	// when
 	let request = r#"{"jsonrpc":"2.0","method":"personal_transactionsToConfirm","params":[],"id":1}"#;
-	let response = r#"{"jsonrpc":"2.0","result":[{"id":"0x01","transaction":{"data":null,"from":"0x0000000000000000000000000000000000000001","gas":"0x989680","gasPrice":"0x2710","nonce":null,"to":"0xd46e8dd67c5d32be8058bb8eb970870f07244567","value":"0x01"}}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"id":"0x01","transaction":{"condition":null,"data":null,"from":"0x0000000000000000000000000000000000000001","gas":"0x989680","gasPrice":"0x2710","nonce":null,"to":"0xd46e8dd67c5d32be8058bb8eb970870f07244567","value":"0x01"}}],"id":1}"#;
 
 	// then
 	assert_eq!(tester.io.handle_request(&request), Some(response.to_owned()));
@@ -102,6 +103,7 @@ fn should_reject_transaction_from_queue_without_dispatching() {
 		value: Some(U256::from(1)),
 		data: None,
 		nonce: None,
+		condition: None,
 	});
 	assert_eq!(tester.queue.requests().len(), 1);
 
@@ -127,6 +129,7 @@ fn should_not_remove_transaction_if_password_is_invalid() {
 		value: Some(U256::from(1)),
 		data: None,
 		nonce: None,
+		condition: None,
 	});
 	assert_eq!(tester.queue.requests().len(), 1);
 
@@ -153,6 +156,7 @@ fn should_confirm_transaction_and_dispatch() {
 		value: Some(U256::from(1)),
 		data: None,
 		nonce: None,
+		condition: None,
 	});
 
 	let t = Transaction {
@@ -0,0 +1,102 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+use jsonrpc_core::IoHandler;
+use util::{KeyPair, Address, U256};
+use ethcore::client::TestBlockChainClient;
+use ethcore::transaction::{Transaction, Action, SignedTransaction};
+use ethcore::miner::{QueuedTransaction, TransactionOrigin, FutureReason};
+use v1::{Txpool, TxpoolClient};
+use v1::tests::helpers::TestMinerService;
+
+fn transaction(keypair: &KeyPair, nonce: u64) -> SignedTransaction {
+	Transaction {
+		action: Action::Call(Address::from(0x10)),
+		value: U256::from(10),
+		data: vec![],
+		gas: U256::from(21_000),
+		gas_price: U256::one(),
+		nonce: U256::from(nonce),
+	}.sign(keypair.secret())
+}
+
+fn txpool_io(miner: &Arc<TestMinerService>) -> IoHandler {
+	let client = Arc::new(TestBlockChainClient::default());
+	let io = IoHandler::new();
+	io.add_delegate(TxpoolClient::new(&client, miner).to_delegate());
+	io
+}
+
+fn queued_miner(keypair: &KeyPair) -> Arc<TestMinerService> {
+	let miner = Arc::new(TestMinerService::default());
+	*miner.queued_transactions.lock() = vec![
+		QueuedTransaction {
+			transaction: transaction(keypair, 0),
+			origin: TransactionOrigin::Local,
+			future: None,
+		},
+		QueuedTransaction {
+			transaction: transaction(keypair, 2),
+			origin: TransactionOrigin::External,
+			future: Some(FutureReason::NonceGap { expected: U256::one() }),
+		},
+	];
+	miner
+}
+
+#[test]
+fn rpc_txpool_status() {
+	let keypair = KeyPair::create().unwrap();
+	let io = txpool_io(&queued_miner(&keypair));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "txpool_status", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"pending":"0x01","queued":"0x01"},"id":1}"#;
+
+	assert_eq!(io.handle_request(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_txpool_content() {
+	let keypair = KeyPair::create().unwrap();
+	let io = txpool_io(&queued_miner(&keypair));
+	let transaction_json = |nonce: u64, nonce_hex: &str| format!(
+		r#"{{"blockHash":null,"blockNumber":null,"creates":null,"from":"0x{:?}","gas":"0x5208","gasPrice":"0x01","hash":"0x{:?}","input":"0x","nonce":"{}","to":"0x0000000000000000000000000000000000000010","transactionIndex":null,"value":"0x0a"}}"#,
+		keypair.address(), transaction(&keypair, nonce).hash(), nonce_hex
+	);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "txpool_content", "params": [], "id": 1}"#;
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","result":{{"pending":{{"0x{:?}":{{"0":{{"origin":"local","reason":null,"transaction":{}}}}}}},"queued":{{"0x{:?}":{{"2":{{"origin":"external","reason":{{"nonceGap":{{"expected":"0x01"}}}},"transaction":{}}}}}}}}},"id":1}}"#,
+		keypair.address(), transaction_json(0, "0x00"), keypair.address(), transaction_json(2, "0x02")
+	);
+
+	assert_eq!(io.handle_request(request), Some(response));
+}
+
+#[test]
+fn rpc_txpool_inspect() {
+	let keypair = KeyPair::create().unwrap();
+	let io = txpool_io(&queued_miner(&keypair));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "txpool_inspect", "params": [], "id": 1}"#;
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","result":{{"pending":{{"0x{:?}":{{"0":"0x0000000000000000000000000000000000000010: 10 wei + 21000 gas × 1 wei"}}}},"queued":{{"0x{:?}":{{"2":"0x0000000000000000000000000000000000000010: 10 wei + 21000 gas × 1 wei"}}}}}},"id":1}}"#,
+		keypair.address(), keypair.address()
+	);
+
+	assert_eq!(io.handle_request(request), Some(response));
+}
@@ -50,6 +50,9 @@ pub trait Eth: Sized + Send + Sync + 'static {
 	/// Returns content of the storage at given address.
 	fn storage_at(&self, _: Params) -> Result<Value, Error>;
 
+	/// Returns Merkle proofs of given account and its storage slots.
+	fn proof(&self, _: Params) -> Result<Value, Error>;
+
 	/// Returns block with given hash.
 	fn block_by_hash(&self, _: Params) -> Result<Value, Error>;
 
@@ -138,6 +141,7 @@ pub trait Eth: Sized + Send + Sync + 'static {
 		delegate.add_method("eth_blockNumber", Eth::block_number);
 		delegate.add_method("eth_getBalance", Eth::balance);
 		delegate.add_method("eth_getStorageAt", Eth::storage_at);
+		delegate.add_method("eth_getProof", Eth::proof);
 		delegate.add_method("eth_getTransactionCount", Eth::transaction_count);
 		delegate.add_method("eth_getBlockTransactionCountByHash", Eth::block_transaction_count_by_hash);
 		delegate.add_method("eth_getBlockTransactionCountByNumber", Eth::block_transaction_count_by_number);
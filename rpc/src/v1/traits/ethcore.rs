@@ -67,6 +67,14 @@ pub trait Ethcore: Sized + Send + Sync + 'static {
 	/// Returns error when signer is disabled
 	fn unsigned_transactions_count(&self, _: Params) -> Result<Value, Error>;
 
+	/// Returns up to `count` accounts in the state of given block (latest by default), starting after `after`.
+	/// Returns null unless the node runs with `--fat-db` and the state is available. `count` may be at most 1000.
+	fn list_accounts(&self, _: Params) -> Result<Value, Error>;
+
+	/// Returns up to `count` storage keys of an account in the state of given block (latest by default), starting after `after`.
+	/// Returns null unless the node runs with `--fat-db` and the state is available. `count` may be at most 1000.
+	fn list_storage_keys(&self, _: Params) -> Result<Value, Error>;
+
 	/// Should be used to convert object to io delegate.
 	fn to_delegate(self) -> IoDelegate<Self> {
 		let mut delegate = IoDelegate::new(Arc::new(self));
@@ -86,6 +94,8 @@ pub trait Ethcore: Sized + Send + Sync + 'static {
 		delegate.add_method("ethcore_defaultExtraData", Ethcore::default_extra_data);
 		delegate.add_method("ethcore_gasPriceStatistics", Ethcore::gas_price_statistics);
 		delegate.add_method("ethcore_unsignedTransactionsCount", Ethcore::unsigned_transactions_count);
+		delegate.add_method("ethcore_listAccounts", Ethcore::list_accounts);
+		delegate.add_method("ethcore_listStorageKeys", Ethcore::list_storage_keys);
 
 		delegate
 	}
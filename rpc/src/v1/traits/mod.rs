@@ -23,7 +23,9 @@ pub mod personal;
 pub mod ethcore;
 pub mod ethcore_set;
 pub mod traces;
+pub mod debug;
 pub mod rpc;
+pub mod txpool;
 
 pub use self::web3::Web3;
 pub use self::eth::{Eth, EthFilter, EthSigning};
@@ -32,6 +34,8 @@ pub use self::personal::{Personal, PersonalSigner};
 pub use self::ethcore::Ethcore;
 pub use self::ethcore_set::EthcoreSet;
 pub use self::traces::Traces;
+pub use self::debug::Debug;
 pub use self::rpc::Rpc;
+pub use self::txpool::Txpool;
 
 
@@ -0,0 +1,103 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+use v1::types::{Bytes, H160, H256, U256};
+use ethcore::client::{AccountProof as EthAccountProof, StorageProof as EthStorageProof};
+
+/// Storage slot proof
+#[derive(Debug, Serialize)]
+pub struct StorageProof {
+	/// Storage key
+	pub key: H256,
+	/// Value
+	pub value: U256,
+	/// RLP-encoded trie nodes
+	pub proof: Vec<Bytes>,
+}
+
+impl From<EthStorageProof> for StorageProof {
+	fn from(p: EthStorageProof) -> Self {
+		StorageProof {
+			key: p.key.into(),
+			value: p.value.into(),
+			proof: p.proof.into_iter().map(Bytes::new).collect(),
+		}
+	}
+}
+
+/// Account proof
+#[derive(Debug, Serialize)]
+pub struct AccountProof {
+	/// Address
+	pub address: H160,
+	/// Balance
+	pub balance: U256,
+	/// Nonce
+	pub nonce: U256,
+	/// Code hash
+	#[serde(rename="codeHash")]
+	pub code_hash: H256,
+	/// Storage root
+	#[serde(rename="storageHash")]
+	pub storage_hash: H256,
+	/// RLP-encoded trie nodes
+	#[serde(rename="accountProof")]
+	pub account_proof: Vec<Bytes>,
+	/// Storage proofs
+	#[serde(rename="storageProof")]
+	pub storage_proof: Vec<StorageProof>,
+}
+
+impl From<EthAccountProof> for AccountProof {
+	fn from(p: EthAccountProof) -> Self {
+		AccountProof {
+			address: p.address.into(),
+			balance: p.balance.into(),
+			nonce: p.nonce.into(),
+			code_hash: p.code_hash.into(),
+			storage_hash: p.storage_hash.into(),
+			account_proof: p.account_proof.into_iter().map(Bytes::new).collect(),
+			storage_proof: p.storage_proof.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use util::{Address, H256, SHA3_EMPTY, SHA3_NULL_RLP};
+	use ethcore::client::{AccountProof as EthAccountProof, StorageProof as EthStorageProof};
+	use super::AccountProof;
+
+	#[test]
+	fn account_proof_serialization() {
+		let proof = AccountProof::from(EthAccountProof {
+			address: Address::from(1),
+			balance: 2.into(),
+			nonce: 0.into(),
+			code_hash: SHA3_EMPTY,
+			storage_hash: SHA3_NULL_RLP,
+			account_proof: vec![vec![0x80]],
+			storage_proof: vec![EthStorageProof {
+				key: H256::from(1),
+				value: H256::from(0x2a),
+				proof: vec![],
+			}],
+		});
+		let serialized = serde_json::to_string(&proof).unwrap();
+		assert_eq!(serialized, r#"{"address":"0x0000000000000000000000000000000000000001","balance":"0x02","nonce":"0x00","codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","storageHash":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421","accountProof":["0x80"],"storageProof":[{"key":"0x0000000000000000000000000000000000000000000000000000000000000001","value":"0x2a","proof":[]}]}"#);
+	}
+}
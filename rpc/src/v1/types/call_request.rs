@@ -14,8 +14,34 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
+use ethcore::client::AccountOverride as EthAccountOverride;
 use v1::helpers::CallRequest as Request;
-use v1::types::{Bytes, H160, U256};
+use v1::types::{Bytes, H160, H256, U256};
+
+/// Account state override
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<U256>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Storage slots
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+impl Into<EthAccountOverride> for AccountOverride {
+	fn into(self) -> EthAccountOverride {
+		EthAccountOverride {
+			balance: self.balance.map(Into::into),
+			nonce: self.nonce.map(Into::into),
+			code: self.code.map(Into::into),
+			storage: self.storage.map(|s| s.into_iter().map(|(k, v)| (k.into(), v.into())).collect()),
+		}
+	}
+}
 
 /// Call request
 #[derive(Debug, Default, PartialEq, Deserialize)]
@@ -35,6 +61,9 @@ pub struct CallRequest {
 	pub data: Option<Bytes>,
 	/// Nonce
 	pub nonce: Option<U256>,
+	/// State overrides
+	#[serde(rename="stateOverride")]
+	pub state_override: Option<BTreeMap<H160, AccountOverride>>,
 }
 
 impl Into<Request> for CallRequest {
@@ -47,6 +76,9 @@ impl Into<Request> for CallRequest {
 			value: self.value.map(Into::into),
 			data: self.data.map(Into::into),
 			nonce: self.nonce.map(Into::into),
+			state_override: self.state_override
+				.map(|o| o.into_iter().map(|(address, account)| (address.into(), account.into())).collect())
+				.unwrap_or_else(BTreeMap::new),
 		}
 	}
 }
@@ -56,8 +88,9 @@ mod tests {
 	use std::str::FromStr;
 	use rustc_serialize::hex::FromHex;
 	use serde_json;
-	use v1::types::{U256, H160};
-	use super::CallRequest;
+	use std::collections::BTreeMap;
+	use v1::types::{U256, H160, H256};
+	use super::{CallRequest, AccountOverride};
 
 	#[test]
 	fn call_request_deserialize() {
@@ -80,6 +113,7 @@ mod tests {
 			value: Some(U256::from(3)),
 			data: Some(vec![0x12, 0x34, 0x56].into()),
 			nonce: Some(U256::from(4)),
+			state_override: None,
 		});
 	}
 
@@ -102,7 +136,8 @@ mod tests {
 			gas: Some(U256::from_str("76c0").unwrap()),
 			value: Some(U256::from_str("9184e72a").unwrap()),
 			data: Some("d46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675".from_hex().unwrap().into()),
-			nonce: None
+			nonce: None,
+			state_override: None,
 		});
 	}
 
@@ -119,6 +154,42 @@ mod tests {
 			value: None,
 			data: None,
 			nonce: None,
+			state_override: None,
+		});
+	}
+
+	#[test]
+	fn call_request_deserialize_state_override() {
+		let s = r#"{
+			"to":"0x0000000000000000000000000000000000000002",
+			"stateOverride":{
+				"0x0000000000000000000000000000000000000002":{
+					"balance":"0x10",
+					"code":"0x6000",
+					"storage":{"0x0000000000000000000000000000000000000000000000000000000000000001":"0x0000000000000000000000000000000000000000000000000000000000000002"}
+				}
+			}
+		}"#;
+		let deserialized: CallRequest = serde_json::from_str(s).unwrap();
+
+		let mut storage = BTreeMap::new();
+		storage.insert(H256::from(1), H256::from(2));
+		let mut state_override = BTreeMap::new();
+		state_override.insert(H160::from(2), AccountOverride {
+			balance: Some(U256::from(0x10)),
+			nonce: None,
+			code: Some(vec![0x60, 0x00].into()),
+			storage: Some(storage),
+		});
+		assert_eq!(deserialized, CallRequest {
+			from: None,
+			to: Some(H160::from(2)),
+			gas_price: None,
+			gas: None,
+			value: None,
+			data: None,
+			nonce: None,
+			state_override: Some(state_override),
 		});
 	}
 }
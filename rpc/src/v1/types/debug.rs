@@ -0,0 +1,145 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-style debug trace types.
+
+use std::collections::BTreeMap;
+use rustc_serialize::hex::ToHex;
+use util::{H256, U256};
+use ethcore::client;
+
+/// Options of `debug_traceTransaction`.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct TraceOptions {
+	/// Don't capture the memory.
+	#[serde(rename="disableMemory")]
+	pub disable_memory: Option<bool>,
+	/// Don't capture the stack.
+	#[serde(rename="disableStack")]
+	pub disable_stack: Option<bool>,
+}
+
+impl Into<client::StructLogOptions> for TraceOptions {
+	fn into(self) -> client::StructLogOptions {
+		client::StructLogOptions {
+			disable_memory: self.disable_memory.unwrap_or(false),
+			disable_stack: self.disable_stack.unwrap_or(false),
+		}
+	}
+}
+
+fn word_to_hex(word: &U256) -> String {
+	H256::from(word).to_hex()
+}
+
+/// State of the VM before executing a single instruction.
+/// Words are serialized as unprefixed 32-byte hex strings.
+#[derive(Debug, Serialize)]
+pub struct StructLog {
+	/// Program counter
+	pub pc: usize,
+	/// Instruction mnemonic
+	pub op: String,
+	/// Gas available
+	pub gas: u64,
+	/// Gas cost of the instruction
+	#[serde(rename="gasCost")]
+	pub gas_cost: u64,
+	/// Call depth
+	pub depth: usize,
+	/// Stack items, bottom first
+	pub stack: Option<Vec<String>>,
+	/// Memory split into words
+	pub memory: Option<Vec<String>>,
+	/// Storage written so far
+	pub storage: BTreeMap<String, String>,
+}
+
+impl From<client::StructLog> for StructLog {
+	fn from(l: client::StructLog) -> Self {
+		StructLog {
+			pc: l.pc,
+			op: l.op,
+			gas: l.gas.low_u64(),
+			gas_cost: l.gas_cost.low_u64(),
+			depth: l.depth,
+			stack: l.stack.map(|s| s.iter().map(word_to_hex).collect()),
+			memory: l.memory.map(|m| m.chunks(32).map(|c| c.to_hex()).collect()),
+			storage: l.storage.iter().map(|(k, v)| (word_to_hex(k), word_to_hex(v))).collect(),
+		}
+	}
+}
+
+/// Result of `debug_traceTransaction`.
+#[derive(Debug, Serialize)]
+pub struct DebugTrace {
+	/// Gas used by the transaction
+	pub gas: u64,
+	/// Transaction output as unprefixed hex
+	#[serde(rename="returnValue")]
+	pub return_value: String,
+	/// Logs of all executed instructions
+	#[serde(rename="structLogs")]
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<client::StructLogTrace> for DebugTrace {
+	fn from(t: client::StructLogTrace) -> Self {
+		DebugTrace {
+			gas: t.gas_used.low_u64(),
+			return_value: t.return_value.to_hex(),
+			struct_logs: t.struct_logs.into_iter().map(StructLog::from).collect(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+	use serde_json;
+	use ethcore::client;
+	use super::{TraceOptions, DebugTrace};
+
+	#[test]
+	fn test_trace_options_deserialize() {
+		let s = r#"{"disableMemory":true}"#;
+		let deserialized: TraceOptions = serde_json::from_str(s).unwrap();
+		let options: client::StructLogOptions = deserialized.into();
+		assert_eq!(options, client::StructLogOptions { disable_memory: true, disable_stack: false });
+	}
+
+	#[test]
+	fn test_debug_trace_serialize() {
+		let mut storage = BTreeMap::new();
+		storage.insert(1.into(), 2.into());
+		let t = DebugTrace::from(client::StructLogTrace {
+			gas_used: 21006.into(),
+			return_value: vec![0x2a],
+			struct_logs: vec![client::StructLog {
+				pc: 2,
+				op: "SSTORE".into(),
+				gas: 100.into(),
+				gas_cost: 20000.into(),
+				depth: 1,
+				stack: Some(vec![0x2a.into()]),
+				memory: None,
+				storage: storage,
+			}],
+		});
+		let serialized = serde_json::to_string(&t).unwrap();
+		assert_eq!(serialized, r#"{"gas":21006,"returnValue":"2a","structLogs":[{"pc":2,"op":"SSTORE","gas":100,"gasCost":20000,"depth":1,"stack":["000000000000000000000000000000000000000000000000000000000000002a"],"memory":null,"storage":{"0000000000000000000000000000000000000000000000000000000000000001":"0000000000000000000000000000000000000000000000000000000000000002"}}]}"#);
+	}
+}
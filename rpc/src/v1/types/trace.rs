@@ -321,6 +321,35 @@ impl From<trace::CreateResult> for CreateResult {
 	}
 }
 
+/// Builtin call result
+#[derive(Debug, Serialize)]
+pub struct BuiltinCallResult {
+	/// Builtin name
+	name: String,
+	/// Gas used
+	#[serde(rename="gasUsed")]
+	gas_used: U256,
+	/// Sizes of decoded arguments
+	#[serde(rename="argSizes")]
+	arg_sizes: Vec<U256>,
+	/// Output bytes
+	output: Bytes,
+	/// Failure reason
+	error: Option<String>,
+}
+
+impl From<trace::BuiltinCallResult> for BuiltinCallResult {
+	fn from(c: trace::BuiltinCallResult) -> Self {
+		BuiltinCallResult {
+			name: c.name,
+			gas_used: c.gas_used.into(),
+			arg_sizes: c.arg_sizes.into_iter().map(Into::into).collect(),
+			output: c.output.into(),
+			error: c.error,
+		}
+	}
+}
+
 /// Response
 #[derive(Debug, Serialize)]
 pub enum Res {
@@ -336,6 +365,9 @@ pub enum Res {
 	/// Creation failure
 	#[serde(rename="failedCreate")]
 	FailedCreate,
+	/// Builtin call
+	#[serde(rename="builtinCall")]
+	BuiltinCall(BuiltinCallResult),
 }
 
 impl From<trace::Res> for Res {
@@ -345,6 +377,7 @@ impl From<trace::Res> for Res {
 			trace::Res::Create(create) => Res::Create(CreateResult::from(create)),
 			trace::Res::FailedCall => Res::FailedCall,
 			trace::Res::FailedCreate => Res::FailedCreate,
+			trace::Res::BuiltinCall(builtin) => Res::BuiltinCall(BuiltinCallResult::from(builtin)),
 		}
 	}
 }
@@ -549,4 +582,27 @@ mod tests {
 		let serialized = serde_json::to_string(&results).unwrap();
 		assert_eq!(serialized, r#"[{"call":{"gasUsed":"0x01","output":"0x1234"}},{"create":{"gasUsed":"0x02","code":"0x4556","address":"0x0000000000000000000000000000000000000003"}},{"failedCall":[]},{"failedCreate":[]}]"#);
 	}
+
+	#[test]
+	fn test_builtin_result_serialize() {
+		let results = vec![
+			Res::BuiltinCall(BuiltinCallResult {
+				name: "zkSNARK".into(),
+				gas_used: U256::from(1),
+				arg_sizes: vec![U256::from(2), U256::from(3), U256::from(0)],
+				output: vec![0x01].into(),
+				error: None,
+			}),
+			Res::BuiltinCall(BuiltinCallResult {
+				name: "zkSNARK".into(),
+				gas_used: U256::from(4),
+				arg_sizes: vec![],
+				output: vec![].into(),
+				error: Some("invalid ABI encoding".into()),
+			}),
+		];
+
+		let serialized = serde_json::to_string(&results).unwrap();
+		assert_eq!(serialized, r#"[{"builtinCall":{"name":"zkSNARK","gasUsed":"0x01","argSizes":["0x02","0x03","0x00"],"output":"0x01","error":null}},{"builtinCall":{"name":"zkSNARK","gasUsed":"0x04","argSizes":[],"output":"0x","error":"invalid ABI encoding"}}]"#);
+	}
 }
@@ -0,0 +1,67 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `TransactionCondition` type
+
+use ethcore::miner;
+
+/// Condition under which a transaction is released to the transaction queue.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TransactionCondition {
+	/// Released once the best block has at least given number.
+	#[serde(rename="block")]
+	Number(u64),
+	/// Released once the best block has at least given timestamp.
+	#[serde(rename="time")]
+	Timestamp(u64),
+}
+
+impl From<miner::TransactionCondition> for TransactionCondition {
+	fn from(c: miner::TransactionCondition) -> Self {
+		match c {
+			miner::TransactionCondition::Number(n) => TransactionCondition::Number(n),
+			miner::TransactionCondition::Timestamp(t) => TransactionCondition::Timestamp(t),
+		}
+	}
+}
+
+impl Into<miner::TransactionCondition> for TransactionCondition {
+	fn into(self) -> miner::TransactionCondition {
+		match self {
+			TransactionCondition::Number(n) => miner::TransactionCondition::Number(n),
+			TransactionCondition::Timestamp(t) => miner::TransactionCondition::Timestamp(t),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::*;
+
+	#[test]
+	fn transaction_condition_deserialize() {
+		let s = r#"[{"block":1},{"time":1500000000}]"#;
+		let deserialized: Vec<TransactionCondition> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![TransactionCondition::Number(1), TransactionCondition::Timestamp(1_500_000_000)]);
+	}
+
+	#[test]
+	fn transaction_condition_serialize() {
+		let serialized = serde_json::to_string(&TransactionCondition::Number(1)).unwrap();
+		assert_eq!(serialized, r#"{"block":1}"#);
+	}
+}
@@ -16,7 +16,7 @@
 
 //! `TransactionRequest` type
 
-use v1::types::{Bytes, H160, U256};
+use v1::types::{Bytes, H160, U256, TransactionCondition};
 use v1::helpers::{TransactionRequest as Request, TransactionConfirmation as Confirmation};
 
 /// Transaction request coming from RPC
@@ -37,6 +37,8 @@ pub struct TransactionRequest {
 	pub data: Option<Bytes>,
 	/// Transaction's nonce
 	pub nonce: Option<U256>,
+	/// Condition under which the transaction is released to the queue
+	pub condition: Option<TransactionCondition>,
 }
 
 impl From<Request> for TransactionRequest {
@@ -49,6 +51,7 @@ impl From<Request> for TransactionRequest {
 			value: r.value.map(Into::into),
 			data: r.data.map(Into::into),
 			nonce: r.nonce.map(Into::into),
+			condition: r.condition.map(Into::into),
 		}
 	}
 }
@@ -63,6 +66,7 @@ impl Into<Request> for TransactionRequest {
 			value: self.value.map(Into::into),
 			data: self.data.map(Into::into),
 			nonce: self.nonce.map(Into::into),
+			condition: self.condition.map(Into::into),
 		}
 	}
 }
@@ -99,7 +103,7 @@ mod tests {
 	use std::str::FromStr;
 	use rustc_serialize::hex::FromHex;
 	use serde_json;
-	use v1::types::{U256, H160};
+	use v1::types::{U256, H160, TransactionCondition};
 	use super::*;
 
 	#[test]
@@ -123,6 +127,7 @@ mod tests {
 			value: Some(U256::from(3)),
 			data: Some(vec![0x12, 0x34, 0x56].into()),
 			nonce: Some(U256::from(4)),
+			condition: None,
 		});
 	}
 
@@ -145,7 +150,8 @@ mod tests {
 			gas: Some(U256::from_str("76c0").unwrap()),
 			value: Some(U256::from_str("9184e72a").unwrap()),
 			data: Some("d46e8dd67c5d32be8d46e8dd67c5d32be8058bb8eb970870f072445675058bb8eb970870f072445675".from_hex().unwrap().into()),
-			nonce: None
+			nonce: None,
+			condition: None,
 		});
 	}
 
@@ -162,6 +168,7 @@ mod tests {
 			value: None,
 			data: None,
 			nonce: None,
+			condition: None,
 		});
 	}
 
@@ -185,6 +192,24 @@ mod tests {
 			value: None,
 			data: Some(vec![0x85, 0x95, 0xba, 0xb1].into()),
 			nonce: None,
+			condition: None,
+		});
+	}
+
+	#[test]
+	fn transaction_request_with_condition_deserialize() {
+		let s = r#"{"from":"0x0000000000000000000000000000000000000001","condition":{"block":13}}"#;
+		let deserialized: TransactionRequest = serde_json::from_str(s).unwrap();
+
+		assert_eq!(deserialized, TransactionRequest {
+			from: H160::from(1).into(),
+			to: None,
+			gas_price: None,
+			gas: None,
+			value: None,
+			data: None,
+			nonce: None,
+			condition: Some(TransactionCondition::Number(13)),
 		});
 	}
 
@@ -0,0 +1,192 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transaction pool inspection types.
+
+use std::collections::BTreeMap;
+use ethcore::miner::{self, QueuedTransaction};
+use ethcore::transaction::Action;
+use v1::types::{H160, U256, Transaction};
+
+/// Origin of a transaction in the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum TransactionOrigin {
+	/// Submitted through this node.
+	#[serde(rename="local")]
+	Local,
+	/// Received from the network.
+	#[serde(rename="external")]
+	External,
+}
+
+impl From<miner::TransactionOrigin> for TransactionOrigin {
+	fn from(origin: miner::TransactionOrigin) -> Self {
+		match origin {
+			miner::TransactionOrigin::Local => TransactionOrigin::Local,
+			miner::TransactionOrigin::External => TransactionOrigin::External,
+		}
+	}
+}
+
+/// Reason why a transaction is not ready to be mined yet.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum FutureReason {
+	/// Nonce is higher than the next nonce expected from the sender.
+	#[serde(rename="nonceGap")]
+	NonceGap {
+		/// Next nonce expected from the sender.
+		expected: U256,
+	},
+	/// Sender cannot cover `value + gas * gas_price`.
+	#[serde(rename="insufficientBalance")]
+	InsufficientBalance {
+		/// Current balance of the sender.
+		balance: U256,
+		/// Maximal cost of the transaction.
+		cost: U256,
+	},
+}
+
+impl From<miner::FutureReason> for FutureReason {
+	fn from(reason: miner::FutureReason) -> Self {
+		match reason {
+			miner::FutureReason::NonceGap { expected } => FutureReason::NonceGap {
+				expected: expected.into(),
+			},
+			miner::FutureReason::InsufficientBalance { balance, cost } => FutureReason::InsufficientBalance {
+				balance: balance.into(),
+				cost: cost.into(),
+			},
+		}
+	}
+}
+
+/// Transaction in the pool.
+#[derive(Debug, Serialize)]
+pub struct TxpoolTransaction {
+	/// Transaction
+	pub transaction: Transaction,
+	/// Origin
+	pub origin: TransactionOrigin,
+	/// Why the transaction is queued; `null` for pending transactions.
+	pub reason: Option<FutureReason>,
+}
+
+impl From<QueuedTransaction> for TxpoolTransaction {
+	fn from(queued: QueuedTransaction) -> Self {
+		TxpoolTransaction {
+			transaction: queued.transaction.into(),
+			origin: queued.origin.into(),
+			reason: queued.future.map(Into::into),
+		}
+	}
+}
+
+/// Pool entries grouped by sender and (decimal) nonce.
+pub type TxpoolGroup<T> = BTreeMap<H160, BTreeMap<String, T>>;
+
+/// Pending (ready to be mined) and queued (future) transactions.
+#[derive(Debug, Serialize)]
+pub struct TxpoolContent<T> where T: ::serde::Serialize {
+	/// Transactions ready to be mined.
+	pub pending: TxpoolGroup<T>,
+	/// Transactions waiting for a nonce gap to be filled or for enough balance.
+	pub queued: TxpoolGroup<T>,
+}
+
+impl<T> TxpoolContent<T> where T: ::serde::Serialize {
+	/// Groups `transactions` by sender and nonce, converting each with `f`.
+	fn new<F>(transactions: Vec<QueuedTransaction>, f: F) -> Self where F: Fn(QueuedTransaction) -> T {
+		let mut content = TxpoolContent {
+			pending: BTreeMap::new(),
+			queued: BTreeMap::new(),
+		};
+		for queued in transactions {
+			let sender = queued.transaction.sender().expect("Transactions in the queue have verified senders.");
+			let nonce = format!("{}", queued.transaction.nonce);
+			let group = match queued.future.is_some() {
+				true => &mut content.queued,
+				false => &mut content.pending,
+			};
+			group.entry(sender.into()).or_insert_with(BTreeMap::new).insert(nonce, f(queued));
+		}
+		content
+	}
+}
+
+impl TxpoolContent<TxpoolTransaction> {
+	/// Full transactions grouped by sender and nonce.
+	pub fn content(transactions: Vec<QueuedTransaction>) -> Self {
+		TxpoolContent::new(transactions, Into::into)
+	}
+}
+
+impl TxpoolContent<String> {
+	/// One-line transaction summaries grouped by sender and nonce.
+	pub fn inspect(transactions: Vec<QueuedTransaction>) -> Self {
+		TxpoolContent::new(transactions, summary)
+	}
+}
+
+/// Returns geth-style one-line summary of a transaction.
+fn summary(queued: QueuedTransaction) -> String {
+	let t = queued.transaction;
+	let to = match t.action {
+		Action::Create => "contract creation".to_owned(),
+		Action::Call(ref address) => format!("0x{:?}", address),
+	};
+	format!("{}: {} wei + {} gas × {} wei", to, t.value, t.gas, t.gas_price)
+}
+
+/// Number of pending and queued transactions.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TxpoolStatus {
+	/// Transactions ready to be mined.
+	pub pending: U256,
+	/// Transactions not ready to be mined yet.
+	pub queued: U256,
+}
+
+impl<'a> From<&'a [QueuedTransaction]> for TxpoolStatus {
+	fn from(transactions: &'a [QueuedTransaction]) -> Self {
+		let queued = transactions.iter().filter(|t| t.future.is_some()).count();
+		TxpoolStatus {
+			pending: U256::from((transactions.len() - queued) as u64),
+			queued: U256::from(queued as u64),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use v1::types::U256;
+	use super::{FutureReason, TxpoolStatus};
+
+	#[test]
+	fn future_reason_serialize() {
+		let reason = FutureReason::NonceGap { expected: U256::from(5) };
+		let serialized = serde_json::to_string(&reason).unwrap();
+		assert_eq!(serialized, r#"{"nonceGap":{"expected":"0x05"}}"#);
+	}
+
+	#[test]
+	fn txpool_status_serialize() {
+		let status = TxpoolStatus { pending: U256::from(2), queued: U256::from(1) };
+		let serialized = serde_json::to_string(&status).unwrap();
+		assert_eq!(serialized, r#"{"pending":"0x02","queued":"0x01"}"#);
+	}
+}
@@ -16,6 +16,7 @@
 
 use hash::H256;
 use sha3::Hashable;
+use bytes::Bytes;
 use hashdb::HashDB;
 use super::{TrieDB, Trie, TrieDBIterator, TrieError};
 use trie::trietraits::TrieItem;
@@ -52,8 +53,9 @@ impl<'db> FatDB<'db> {
 }
 
 impl<'db> Trie for FatDB<'db> {
+	/// Keys whose preimage is missing are skipped; use `FatDB::iter` to detect them.
 	fn iter<'a>(&'a self) -> Box<Iterator<Item = TrieItem> + 'a> {
-		Box::new(FatDB::iter(self))
+		Box::new(FatDB::iter(self).filter_map(Result::ok))
 	}
 
 	fn root(&self) -> &H256 {
@@ -67,6 +69,10 @@ impl<'db> Trie for FatDB<'db> {
 	fn get<'a, 'key>(&'a self, key: &'key [u8]) -> Option<&'a [u8]> where 'a: 'key {
 		self.raw.get(&key.sha3())
 	}
+
+	fn get_with_proof<'a, 'key>(&'a self, key: &'key [u8]) -> (Option<&'a [u8]>, Vec<Bytes>) where 'a: 'key {
+		self.raw.get_with_proof(&key.sha3())
+	}
 }
 
 /// Itarator over inserted pairs of key values.
@@ -83,15 +89,24 @@ impl<'db> FatDBIterator<'db> {
 			trie: trie,
 		}
 	}
+
+	/// Position the iterator so that the next item is the first one following `key` in trie order,
+	/// or `key` itself if present. Keys are ordered by their hashes.
+	pub fn seek(&mut self, key: &[u8]) {
+		self.trie_iterator.seek(&key.sha3());
+	}
 }
 
 impl<'db> Iterator for FatDBIterator<'db> {
-	type Item = (Vec<u8>, &'db [u8]);
+	/// Key and value, or an error if the preimage of the key hash is not stored in the database.
+	type Item = Result<(Vec<u8>, &'db [u8]), TrieError>;
 
 	fn next(&mut self) -> Option<Self::Item> {
 		self.trie_iterator.next()
 			.map(|(hash, value)| {
-				(self.trie.db().get_aux(&hash).expect("Missing fatdb hash"), value)
+				self.trie.db().get_aux(&hash)
+					.map(|key| (key, value))
+					.ok_or_else(|| TrieError::MissingPreimage(H256::from_slice(&hash)))
 			})
 	}
 }
@@ -109,5 +124,50 @@ fn fatdb_to_trie() {
 	}
 	let t = FatDB::new(&memdb, &root).unwrap();
 	assert_eq!(t.get(&[0x01u8, 0x23]).unwrap(), &[0x01u8, 0x23]);
-	assert_eq!(t.iter().collect::<Vec<_>>(), vec![(vec![0x01u8, 0x23], &[0x01u8, 0x23] as &[u8])]);
+	assert_eq!(t.iter().map(Result::unwrap).collect::<Vec<_>>(), vec![(vec![0x01u8, 0x23], &[0x01u8, 0x23] as &[u8])]);
+}
+
+#[test]
+fn fatdb_seek() {
+	use memorydb::MemoryDB;
+	use trie::{FatDBMut, TrieMut};
+
+	let mut memdb = MemoryDB::new();
+	let mut root = H256::default();
+	let keys: Vec<_> = (0u8..8).map(|i| vec![i]).collect();
+	{
+		let mut t = FatDBMut::new(&mut memdb, &mut root);
+		for k in &keys {
+			t.insert(k, k);
+		}
+	}
+	let t = FatDB::new(&memdb, &root).unwrap();
+	let all: Vec<_> = t.iter().map(|i| i.unwrap().0).collect();
+	assert_eq!(all.len(), keys.len());
+	for (n, k) in all.iter().enumerate() {
+		let mut iter = t.iter();
+		iter.seek(k);
+		assert_eq!(iter.map(|i| i.unwrap().0).collect::<Vec<_>>(), all[n..].to_vec());
+	}
+}
+
+#[test]
+fn fatdb_missing_preimage() {
+	use memorydb::MemoryDB;
+	use trie::{TrieDBMut, TrieMut};
+
+	let mut memdb = MemoryDB::new();
+	let mut root = H256::default();
+	{
+		// a plain trie keyed by hashes looks like a fat DB without the preimages
+		let mut t = TrieDBMut::new(&mut memdb, &mut root);
+		t.insert(&[0x01u8, 0x23].sha3(), &[0x01u8, 0x23]);
+	}
+	let t = FatDB::new(&memdb, &root).unwrap();
+	assert_eq!(t.get(&[0x01u8, 0x23]).unwrap(), &[0x01u8, 0x23]);
+	match t.iter().next() {
+		Some(Err(TrieError::MissingPreimage(hash))) => assert_eq!(hash, [0x01u8, 0x23].sha3()),
+		_ => panic!("expected a missing preimage"),
+	}
+	assert_eq!(Trie::iter(&t).count(), 0);
 }
@@ -36,6 +36,8 @@ pub mod triedbmut;
 pub mod sectriedb;
 /// Export the sectriedbmut module.
 pub mod sectriedbmut;
+/// Export the proof module.
+pub mod proof;
 
 mod fatdb;
 mod fatdbmut;
@@ -48,17 +50,23 @@ pub use self::sectriedbmut::SecTrieDBMut;
 pub use self::sectriedb::SecTrieDB;
 pub use self::fatdb::{FatDB, FatDBIterator};
 pub use self::fatdbmut::FatDBMut;
+pub use self::proof::{verify_proof, ProofError};
 
 /// Trie Errors
 #[derive(Debug)]
 pub enum TrieError {
 	/// Attempted to create a trie with a state root not in the DB.
 	InvalidStateRoot,
+	/// A fat trie contains a key hash whose preimage was never stored in the DB.
+	MissingPreimage(H256),
 }
 
 impl fmt::Display for TrieError {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "Trie Error: Invalid state root.")
+		match *self {
+			TrieError::InvalidStateRoot => write!(f, "Trie Error: Invalid state root."),
+			TrieError::MissingPreimage(ref hash) => write!(f, "Trie Error: Missing preimage of key hash {}.", hash),
+		}
 	}
 }
 
@@ -120,4 +128,12 @@ impl TrieFactory {
 			TrieSpec::Fat => Ok(Box::new(try!(FatDBMut::from_existing(db, root)))),
 		}
 	}
+
+	/// Returns true iff the trie DB is a fat DB (allows enumeration of keys).
+	pub fn is_fat(&self) -> bool {
+		match self.spec {
+			TrieSpec::Fat => true,
+			_ => false,
+		}
+	}
 }
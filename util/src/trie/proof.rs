@@ -0,0 +1,192 @@
+// Copyright 2015, 2016 Ethcore (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Verification of Merkle proofs produced by `Trie::get_with_proof`.
+
+use std::fmt;
+use std::collections::HashMap;
+use hash::H256;
+use bytes::Bytes;
+use sha3::Hashable;
+use rlp::*;
+use nibbleslice::NibbleSlice;
+use super::node::Node;
+
+/// Proof verification errors.
+#[derive(Debug, PartialEq)]
+pub enum ProofError {
+	/// A node required to follow the key is not part of the proof.
+	MissingNode(H256),
+	/// A node of the proof is not a valid trie node.
+	InvalidNode,
+}
+
+impl From<DecoderError> for ProofError {
+	fn from(_: DecoderError) -> Self {
+		ProofError::InvalidNode
+	}
+}
+
+impl fmt::Display for ProofError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ProofError::MissingNode(ref hash) => write!(f, "Proof Error: missing node {}.", hash),
+			ProofError::InvalidNode => write!(f, "Proof Error: invalid trie node."),
+		}
+	}
+}
+
+/// Decodes an untrusted node without panicking on malformed RLP.
+fn decode_node(data: &[u8]) -> Result<Node, ProofError> {
+	let r = UntrustedRlp::new(data);
+	if try!(r.payload_info()).total() != data.len() {
+		return Err(ProofError::InvalidNode);
+	}
+	if r.is_data() {
+		return match r.is_empty() {
+			true => Ok(Node::Empty),
+			false => Err(ProofError::InvalidNode),
+		};
+	}
+	match r.item_count() {
+		2 => {
+			let key = try!(try!(r.at(0)).data());
+			if key.is_empty() {
+				return Err(ProofError::InvalidNode);
+			}
+			let item = try!(r.at(1));
+			match NibbleSlice::from_encoded(key) {
+				(slice, true) => Ok(Node::Leaf(slice, try!(item.data()))),
+				(slice, false) => Ok(Node::Extension(slice, item.as_raw())),
+			}
+		},
+		17 => {
+			let empty: &[u8] = &[];
+			let mut children = [empty; 16];
+			for (i, child) in children.iter_mut().enumerate() {
+				*child = try!(r.at(i)).as_raw();
+			}
+			let value = try!(r.at(16));
+			Ok(Node::Branch(children, if value.is_empty() { None } else { Some(try!(value.data())) }))
+		},
+		_ => Err(ProofError::InvalidNode),
+	}
+}
+
+/// Verifies `proof` of `key` against trie `root`.
+/// Returns the value of `key`, or `None` if the proof shows it is not in the trie.
+/// For secure tries `key` must already be hashed.
+pub fn verify_proof(root: &H256, key: &[u8], proof: &[Bytes]) -> Result<Option<Bytes>, ProofError> {
+	let nodes: HashMap<H256, &[u8]> = proof.iter().map(|node| (node.sha3(), &node[..])).collect();
+	let lookup = |hash: &H256| nodes.get(hash).cloned().ok_or_else(|| ProofError::MissingNode(hash.clone()));
+
+	let mut key = NibbleSlice::new(key);
+	let mut data = try!(lookup(root));
+	loop {
+		let child = match try!(decode_node(data)) {
+			Node::Empty => return Ok(None),
+			Node::Leaf(ref slice, value) => return Ok(match key == *slice {
+				true => Some(value.to_vec()),
+				false => None,
+			}),
+			Node::Extension(ref slice, item) => match key.starts_with(slice) {
+				true => {
+					key = key.mid(slice.len());
+					item
+				},
+				false => return Ok(None),
+			},
+			Node::Branch(ref children, value) => match key.is_empty() {
+				true => return Ok(value.map(|v| v.to_vec())),
+				false => {
+					let child = children[key.at(0) as usize];
+					key = key.mid(1);
+					child
+				},
+			},
+		};
+
+		// children are either hashes of nodes in the proof or inline nodes.
+		let r = UntrustedRlp::new(child);
+		data = match r.is_data() && try!(r.payload_info()).value_len == 32 {
+			true => try!(lookup(&try!(r.as_val::<H256>()))),
+			false => child,
+		};
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use hash::H256;
+	use sha3::Hashable;
+	use hashdb::HashDB;
+	use memorydb::MemoryDB;
+	use super::super::{Trie, TrieMut, TrieDB, TrieDBMut, SecTrieDB, SecTrieDBMut};
+	use super::{verify_proof, ProofError};
+
+	fn populate(t: &mut TrieMut) {
+		t.insert(b"doe", b"reindeer");
+		t.insert(b"dog", b"puppy");
+		t.insert(b"dogglesworth", b"cat");
+		t.insert(b"horse", &[0x42; 40]);
+	}
+
+	#[test]
+	fn should_prove_existing_and_missing_keys() {
+		let mut memdb = MemoryDB::new();
+		let mut root = H256::new();
+		populate(&mut TrieDBMut::new(&mut memdb, &mut root));
+		let t = TrieDB::new(&memdb, &root).unwrap();
+
+		for key in &[&b"doe"[..], &b"dog"[..], &b"dogglesworth"[..], &b"horse"[..], &b"do"[..], &b"dogs"[..], &b"zebra"[..]] {
+			let (value, proof) = t.get_with_proof(key);
+			assert_eq!(proof[0], memdb.get(&root).unwrap().to_vec());
+			assert_eq!(verify_proof(&root, key, &proof), Ok(value.map(|v| v.to_vec())));
+		}
+	}
+
+	#[test]
+	fn should_prove_secure_trie_with_hashed_key() {
+		let mut memdb = MemoryDB::new();
+		let mut root = H256::new();
+		populate(&mut SecTrieDBMut::new(&mut memdb, &mut root));
+		let t = SecTrieDB::new(&memdb, &root).unwrap();
+
+		let (value, proof) = t.get_with_proof(b"dog");
+		assert_eq!(value, Some(&b"puppy"[..]));
+		assert_eq!(verify_proof(&root, &b"dog".sha3(), &proof), Ok(Some(b"puppy".to_vec())));
+	}
+
+	#[test]
+	fn should_reject_incomplete_proof() {
+		let mut memdb = MemoryDB::new();
+		let mut root = H256::new();
+		populate(&mut TrieDBMut::new(&mut memdb, &mut root));
+		let t = TrieDB::new(&memdb, &root).unwrap();
+
+		let (_, proof) = t.get_with_proof(b"horse");
+		assert!(proof.len() > 1);
+		assert_eq!(verify_proof(&root, b"horse", &proof[1..]), Err(ProofError::MissingNode(root.clone())));
+		assert_eq!(verify_proof(&root, b"horse", &[vec![0xf8, 0x01]]), Err(ProofError::MissingNode(root.clone())));
+	}
+
+	#[test]
+	fn should_reject_malformed_node() {
+		let malformed = vec![0xc3, 0x80];
+		let root = malformed.sha3();
+		assert_eq!(verify_proof(&root, b"horse", &[malformed]), Err(ProofError::InvalidNode));
+	}
+}
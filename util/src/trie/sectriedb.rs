@@ -16,6 +16,7 @@
 
 use hash::H256;
 use sha3::Hashable;
+use bytes::Bytes;
 use hashdb::HashDB;
 use super::triedb::TrieDB;
 use super::trietraits::{Trie, TrieItem};
@@ -63,6 +64,10 @@ impl<'db> Trie for SecTrieDB<'db> {
 	fn get<'a, 'key>(&'a self, key: &'key [u8]) -> Option<&'a [u8]> where 'a: 'key {
 		self.raw.get(&key.sha3())
 	}
+
+	fn get_with_proof<'a, 'key>(&'a self, key: &'key [u8]) -> (Option<&'a [u8]>, Vec<Bytes>) where 'a: 'key {
+		self.raw.get_with_proof(&key.sha3())
+	}
 }
 
 #[test]
@@ -182,29 +182,48 @@ impl<'db> TrieDB<'db> {
 	}
 
 	/// Return optional data for a key given as a `NibbleSlice`. Returns `None` if no data exists.
-	fn do_lookup<'a, 'key>(&'a self, key: &NibbleSlice<'key>) -> Option<&'a [u8]> where 'a: 'key {
+	/// All nodes fetched from the database on the way, including the root, are pushed to `proof` if given.
+	fn do_lookup<'a, 'key>(&'a self, key: &NibbleSlice<'key>, mut proof: Option<&mut Vec<Bytes>>) -> Option<&'a [u8]> where 'a: 'key {
 		let root_rlp = self.root_data();
-		self.get_from_node(&root_rlp, key)
+		if let Some(ref mut proof) = proof {
+			proof.push(root_rlp.to_vec());
+		}
+		self.get_from_node(&root_rlp, key, &mut proof)
 	}
 
 	/// Recursible function to retrieve the value given a `node` and a partial `key`. `None` if no
 	/// value exists for the key.
 	///
 	/// Note: Not a public API; use Trie trait functions.
-	fn get_from_node<'a, 'key>(&'a self, node: &'a [u8], key: &NibbleSlice<'key>) -> Option<&'a [u8]> where 'a: 'key {
+	fn get_from_node<'a, 'key>(&'a self, node: &'a [u8], key: &NibbleSlice<'key>, proof: &mut Option<&mut Vec<Bytes>>) -> Option<&'a [u8]> where 'a: 'key {
 		match Node::decoded(node) {
 			Node::Leaf(ref slice, ref value) if key == slice => Some(value),
 			Node::Extension(ref slice, ref item) if key.starts_with(slice) => {
-				self.get_from_node(self.get_raw_or_lookup(item), &key.mid(slice.len()))
+				self.get_from_node(self.get_raw_or_lookup_recorded(item, proof), &key.mid(slice.len()), proof)
 			},
 			Node::Branch(ref nodes, value) => match key.is_empty() {
 				true => value,
-				false => self.get_from_node(self.get_raw_or_lookup(nodes[key.at(0) as usize]), &key.mid(1))
+				false => self.get_from_node(self.get_raw_or_lookup_recorded(nodes[key.at(0) as usize], proof), &key.mid(1), proof)
 			},
 			_ => None
 		}
 	}
 
+	/// Same as `get_raw_or_lookup`, but also pushes the node to `proof` if it had to be looked up.
+	fn get_raw_or_lookup_recorded<'a>(&'a self, node: &'a [u8], proof: &mut Option<&mut Vec<Bytes>>) -> &'a [u8] {
+		let r = Rlp::new(node);
+		match r.is_data() && r.size() == 32 {
+			true => {
+				let data = self.get_raw_or_lookup(node);
+				if let Some(ref mut proof) = *proof {
+					proof.push(data.to_vec());
+				}
+				data
+			},
+			false => node
+		}
+	}
+
 	/// Given some node-describing data `node`, return the actual node RLP.
 	/// This could be a simple identity operation in the case that the node is sufficiently small, but
 	/// may require a database lookup.
@@ -278,6 +297,48 @@ impl<'a> TrieDBIterator<'a> {
 		}
 	}
 
+	/// Position the iterator so that the next item is the first one with a key not less than `key`.
+	pub fn seek(&mut self, key: &[u8]) {
+		self.trail.clear();
+		self.key_nibbles.clear();
+		let root = self.db.root_data();
+		self.seek_descend(root, &NibbleSlice::new(key));
+	}
+
+	/// Descend into a payload, skipping over all items with a key less than `key`.
+	fn seek_descend(&mut self, d: &'a [u8], key: &NibbleSlice) {
+		let node = self.db.get_node(d);
+		let (status, child) = match node {
+			Node::Leaf(ref slice, _) => {
+				self.key_nibbles.extend(slice.iter());
+				(if *slice < *key { Status::Exiting } else { Status::Entering }, None)
+			},
+			Node::Extension(ref slice, item) => {
+				self.key_nibbles.extend(slice.iter());
+				if key.starts_with(slice) {
+					(Status::At, Some((item, slice.len())))
+				} else if *slice < *key {
+					(Status::Exiting, None)
+				} else {
+					(Status::Entering, None)
+				}
+			},
+			Node::Branch(ref children, _) if !key.is_empty() => {
+				let i = key.at(0) as usize;
+				self.key_nibbles.push(i as u8);
+				(Status::AtChild(i), if children[i].len() > 0 { Some((children[i], 1)) } else { None })
+			},
+			_ => (Status::Entering, None),
+		};
+		self.trail.push(Crumb {
+			status: status,
+			node: node,
+		});
+		if let Some((d, consumed)) = child {
+			self.seek_descend(d, &key.mid(consumed));
+		}
+	}
+
 	/// Descend into a payload and get the next item.
 	fn descend_next(&mut self, d: &'a [u8]) -> Option<(Bytes, &'a [u8])> { self.descend(d); self.next() }
 
@@ -347,7 +408,13 @@ impl<'db> Trie for TrieDB<'db> {
 	}
 
 	fn get<'a, 'key>(&'a self, key: &'key [u8]) -> Option<&'a [u8]> where 'a: 'key {
-		self.do_lookup(&NibbleSlice::new(key))
+		self.do_lookup(&NibbleSlice::new(key), None)
+	}
+
+	fn get_with_proof<'a, 'key>(&'a self, key: &'key [u8]) -> (Option<&'a [u8]>, Vec<Bytes>) where 'a: 'key {
+		let mut proof = vec![];
+		let value = self.do_lookup(&NibbleSlice::new(key), Some(&mut proof));
+		(value, proof)
 	}
 }
 
@@ -379,3 +446,35 @@ fn iterator() {
 	assert_eq!(d.iter().map(|i|i.to_vec()).collect::<Vec<_>>(), TrieDB::new(&memdb, &root).unwrap().iter().map(|x|x.0).collect::<Vec<_>>());
 	assert_eq!(d, TrieDB::new(&memdb, &root).unwrap().iter().map(|x|x.1).collect::<Vec<_>>());
 }
+
+#[test]
+fn iterator_seek() {
+	use super::trietraits::TrieMut;
+	use memorydb::*;
+	use super::triedbmut::*;
+
+	let d = vec![ &b"A"[..], &b"AA"[..], &b"AB"[..], &b"B"[..] ];
+
+	let mut memdb = MemoryDB::new();
+	let mut root = H256::new();
+	{
+		let mut t = TrieDBMut::new(&mut memdb, &mut root);
+		for x in &d {
+			t.insert(&x, &x);
+		}
+	}
+	let t = TrieDB::new(&memdb, &root).unwrap();
+	let seek = |key: &[u8]| {
+		let mut iter = t.iter();
+		iter.seek(key);
+		iter.map(|x| x.0).collect::<Vec<_>>()
+	};
+	assert_eq!(seek(b""), vec![b"A".to_vec(), b"AA".to_vec(), b"AB".to_vec(), b"B".to_vec()]);
+	assert_eq!(seek(b"A"), vec![b"A".to_vec(), b"AA".to_vec(), b"AB".to_vec(), b"B".to_vec()]);
+	assert_eq!(seek(b"AA"), vec![b"AA".to_vec(), b"AB".to_vec(), b"B".to_vec()]);
+	assert_eq!(seek(b"AAA"), vec![b"AB".to_vec(), b"B".to_vec()]);
+	assert_eq!(seek(b"AB"), vec![b"AB".to_vec(), b"B".to_vec()]);
+	assert_eq!(seek(b"AC"), vec![b"B".to_vec()]);
+	assert_eq!(seek(b"B"), vec![b"B".to_vec()]);
+	assert_eq!(seek(b"C"), Vec::<Bytes>::new());
+}
@@ -15,6 +15,7 @@
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
 use hash::H256;
+use bytes::Bytes;
 use rlp::SHA3_NULL_RLP;
 
 /// Trie-Item type.
@@ -34,6 +35,10 @@ pub trait Trie {
 	/// What is the value of the given key in this trie?
 	fn get<'a, 'key>(&'a self, key: &'key [u8]) -> Option<&'a [u8]> where 'a: 'key;
 
+	/// Same as `get`, but also returns the RLP of every node fetched from the database during the
+	/// lookup, root first. Together they prove the value (or its absence) against the root.
+	fn get_with_proof<'a, 'key>(&'a self, key: &'key [u8]) -> (Option<&'a [u8]>, Vec<Bytes>) where 'a: 'key;
+
 	/// Returns an iterator over elements of trie.
 	fn iter<'a>(&'a self) -> Box<Iterator<Item = TrieItem> + 'a>;
 }